@@ -0,0 +1,35 @@
+// FIXME: Make me compile! Diff budget: 4 lines.
+
+// no_std1.rs
+// A kernel has no operating system underneath it, so it has no `std`
+// either: no files, no threads, not even a heap until the kernel sets one
+// up. What is left is `core`, the part of the standard library that only
+// needs the CPU. This library is built for the Raspberry Pi's
+// `aarch64-unknown-none` target, where `std` does not exist at all.
+// Make it build there without changing what the functions do.
+// Execute `rustlings hint no_std1` for hints!
+
+// I AM NOT DONE
+
+#![no_std]
+
+use std::cmp::max;
+use std::fmt::{self, Write};
+
+/// Returns the largest of `values`, or `None` if there are none.
+pub fn largest(values: &[u32]) -> Option<u32> {
+    values
+        .iter()
+        .fold(None, |largest, &v| Some(largest.map_or(v, |l| max(l, v))))
+}
+
+/// Returns the sum of the squares of `values`.
+pub fn sum_of_squares(values: &[u32]) -> u64 {
+    let squares: Vec<u64> = values.iter().map(|&v| u64::from(v) * u64::from(v)).collect();
+    squares.iter().sum()
+}
+
+/// Writes `address` as a `0x`-prefixed, zero-padded 64-bit hex number.
+pub fn write_address(out: &mut dyn Write, address: u64) -> fmt::Result {
+    write!(out, "{:#018x}", address)
+}
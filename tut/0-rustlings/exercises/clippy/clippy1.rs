@@ -0,0 +1,21 @@
+// clippy1.rs
+// Clippy is a collection of lints that catch common mistakes and unidiomatic
+// code. This exercise passes once Clippy has nothing left to say about it.
+// Make me pass Clippy! Execute the command `rustlings hint clippy1` if you want a hint :)
+
+// I AM NOT DONE
+
+fn area(radius: f64) -> f64 {
+    let pi = 3.14159;
+    return pi * radius * radius;
+}
+
+fn main() {
+    let radii: Vec<f64> = vec![1.0, 2.5, 4.0];
+    if radii.len() == 0 {
+        println!("Nothing to measure.");
+    }
+    for radius in radii.iter() {
+        println!("A circle of radius {} has an area of {:.2}", radius, area(*radius));
+    }
+}
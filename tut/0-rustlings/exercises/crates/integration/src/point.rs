@@ -0,0 +1,17 @@
+/// Parses a point written as `x,y`, e.g. `3,-4`.
+pub fn parse(input: &str) -> Option<(i32, i32)> {
+    let mut parts = input.split(',');
+    let x = parts.next()?.trim().parse().ok()?;
+    let y = parts.next()?.trim().parse().ok()?;
+    Some((x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parses_negative_numbers() {
+        assert_eq!(parse("-1,-2"), Some((-1, -2)));
+    }
+}
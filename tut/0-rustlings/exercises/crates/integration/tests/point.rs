@@ -0,0 +1,22 @@
+use integration::point;
+
+#[test]
+fn parses_a_point() {
+    assert_eq!(point::parse("3,4"), Some((3, 4)));
+}
+
+#[test]
+fn allows_spaces() {
+    assert_eq!(point::parse(" 3 , 4 "), Some((3, 4)));
+}
+
+#[test]
+fn rejects_garbage() {
+    assert_eq!(point::parse("three,4"), None);
+    assert_eq!(point::parse("3"), None);
+}
+
+#[test]
+fn rejects_extra_coordinates() {
+    assert_eq!(point::parse("1,2,3"), None);
+}
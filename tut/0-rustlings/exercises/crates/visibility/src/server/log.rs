@@ -0,0 +1,3 @@
+pub(super) fn info(message: &str) {
+    println!("[info] {}", message);
+}
@@ -24,6 +24,7 @@ impl FooToo for Dummy {}
 fn ufcs() {
     let dummy = Dummy;
 
+    // Fix the following two lines.
     let x = dummy.foo();
     let y = dummy.foo();
 
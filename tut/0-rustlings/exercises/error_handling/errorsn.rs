@@ -3,7 +3,7 @@
 // You can do it! :)
 //
 // Edit the `read_and_validate` function so that it compiles and
-// passes the tests... so many things could go wrong!
+// handles every input it is given... so many things could go wrong!
 //
 // - Reading from stdin could produce an io::Error
 // - Parsing the input could produce a num::ParseIntError
@@ -20,8 +20,9 @@
 use std::error;
 use std::fmt;
 use std::io;
+use std::process;
 
-// PositiveNonzeroInteger is a struct defined below the tests.
+// PositiveNonzeroInteger is a struct defined below `main`.
 fn read_and_validate(b: &mut dyn io::BufRead) -> Result<PositiveNonzeroInteger, ???> {
     let mut line = String::new();
     b.read_line(&mut line);
@@ -30,41 +31,19 @@ fn read_and_validate(b: &mut dyn io::BufRead) -> Result<PositiveNonzeroInteger,
     answer
 }
 
-// This is a test helper function that turns a &str into a BufReader.
-fn test_with_str(s: &str) -> Result<PositiveNonzeroInteger, Box<dyn error::Error>> {
-    let mut b = io::BufReader::new(s.as_bytes());
-    read_and_validate(&mut b)
-}
-
-#[test]
-fn test_success() {
-    let x = test_with_str("42\n");
-    assert_eq!(PositiveNonzeroInteger(42), x.unwrap());
-}
-
-#[test]
-fn test_not_num() {
-    let x = test_with_str("eleven billion\n");
-    assert!(x.is_err());
-}
-
-#[test]
-fn test_non_positive() {
-    let x = test_with_str("-40\n");
-    assert!(x.is_err());
-}
-
-#[test]
-fn test_ioerror() {
-    struct Broken;
-    impl io::Read for Broken {
-        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
-            Err(io::Error::new(io::ErrorKind::BrokenPipe, "uh-oh!"))
+// Reads a number from stdin and reports whether it is valid. Each input the
+// runner feeds in must print the right line and exit with the right code.
+fn main() {
+    let stdin = io::stdin();
+    let result: Result<PositiveNonzeroInteger, Box<dyn error::Error>> =
+        read_and_validate(&mut stdin.lock());
+    match result {
+        Ok(number) => println!("Valid: {:?}", number),
+        Err(e) => {
+            println!("Invalid: {}", e);
+            process::exit(1);
         }
     }
-    let mut b = io::BufReader::new(Broken);
-    assert!(read_and_validate(&mut b).is_err());
-    assert_eq!("uh-oh!", read_and_validate(&mut b).unwrap_err().to_string());
 }
 
 #[derive(PartialEq, Debug)]
@@ -82,16 +61,6 @@ impl PositiveNonzeroInteger {
     }
 }
 
-#[test]
-fn test_positive_nonzero_integer_creation() {
-    assert!(PositiveNonzeroInteger::new(10).is_ok());
-    assert_eq!(
-        Err(CreationError::Negative),
-        PositiveNonzeroInteger::new(-10)
-    );
-    assert_eq!(Err(CreationError::Zero), PositiveNonzeroInteger::new(0));
-}
-
 #[derive(PartialEq, Debug)]
 enum CreationError {
     Negative,
@@ -0,0 +1,367 @@
+use std::fmt;
+use std::io;
+
+use regex::Regex;
+
+use crate::diff::{self, Line};
+use crate::exercise::Exercise;
+use crate::pristine;
+
+const BUDGET_REGEX: &str = r"(?m)Diff budget:\s*(.+?)\.?\s*$";
+const ITEM_REGEX: &str = r"^(\d+)\s+(line additions?|lines?|characters?)$";
+
+/// The most a student may change an exercise, as declared in its
+/// `// FIXME: ... Diff budget: ...` header.
+///
+/// A `lines` budget counts every changed line. Without one, added lines are
+/// charged to `additions` and edits within existing lines to `characters`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    pub lines: Option<usize>,
+    pub additions: Option<usize>,
+    pub characters: Option<usize>,
+}
+
+/// How much of a budget a student's changes use.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    pub lines: usize,
+    pub additions: usize,
+    pub characters: usize,
+}
+
+impl Budget {
+    /// Parses the diff budget declared in `source`, if there is one.
+    pub fn parse(source: &str) -> Option<Budget> {
+        let spec = Regex::new(BUDGET_REGEX).unwrap().captures(source)?;
+        let item = Regex::new(ITEM_REGEX).unwrap();
+
+        let mut budget = Budget::default();
+        for part in spec[1].split(" and ") {
+            let caps = item.captures(part.trim())?;
+            let count = caps[1].parse().ok()?;
+            match &caps[2] {
+                "line" | "lines" => budget.lines = Some(count),
+                "line addition" | "line additions" => budget.additions = Some(count),
+                _ => budget.characters = Some(count),
+            }
+        }
+        Some(budget)
+    }
+
+    /// Measures a diff of the exercise in the units of this budget.
+    pub fn usage(&self, lines: &[Line]) -> Usage {
+        let mut usage = Usage::default();
+        for hunk in diff::hunks(lines) {
+            let paired = hunk.removed.len().min(hunk.added.len());
+            usage.lines += hunk.removed.len().max(hunk.added.len());
+            for (old, new) in hunk.removed.iter().zip(&hunk.added) {
+                usage.characters += diff::char_distance(old, new);
+            }
+            for removed in &hunk.removed[paired..] {
+                usage.characters += removed.trim().chars().count();
+            }
+            for added in &hunk.added[paired..] {
+                if self.additions.is_some() {
+                    usage.additions += 1;
+                } else {
+                    usage.characters += added.trim().chars().count();
+                }
+            }
+        }
+        usage
+    }
+
+    /// Returns whether `usage` fits within this budget.
+    pub fn allows(&self, usage: &Usage) -> bool {
+        if let Some(lines) = self.lines {
+            return usage.lines <= lines;
+        }
+        usage.additions <= self.additions.unwrap_or(0)
+            && usage.characters <= self.characters.unwrap_or(0)
+    }
+
+    /// Formats `usage` in the same terms as this budget.
+    pub fn describe(&self, usage: &Usage) -> String {
+        let used = Budget {
+            lines: self.lines.map(|_| usage.lines),
+            additions: self.additions.map(|_| usage.additions),
+            characters: self.characters.map(|_| usage.characters),
+        };
+        used.to_string()
+    }
+}
+
+impl fmt::Display for Budget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let plural =
+            |n: usize, unit: &str| format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" });
+        let parts: Vec<String> = vec![
+            self.lines.map(|n| plural(n, "line")),
+            self.additions.map(|n| plural(n, "line addition")),
+            self.characters.map(|n| plural(n, "character")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        write!(f, "{}", parts.join(" and "))
+    }
+}
+
+/// The result of comparing an exercise against its pristine version.
+pub struct BudgetReport {
+    pub budget: Budget,
+    pub usage: Usage,
+    original: String,
+    current: String,
+}
+
+impl BudgetReport {
+    /// Returns whether the student's changes fit the budget.
+    pub fn within(&self) -> bool {
+        self.budget.allows(&self.usage)
+    }
+
+    /// Prints the student's changes against the pristine exercise.
+    pub fn print_diff(&self) {
+        let original = significant_lines(&self.original);
+        let current = significant_lines(&self.current);
+        diff::print(&diff::diff(&original, &current));
+    }
+}
+
+/// Compares an exercise against its pristine version. Returns `None` if the
+/// exercise declares no diff budget, as crate exercises never do.
+pub fn check(exercise: &Exercise) -> io::Result<Option<BudgetReport>> {
+    if exercise.is_crate() {
+        return Ok(None);
+    }
+    let original = pristine::original_source(&exercise.path)?;
+    let current = std::fs::read_to_string(&exercise.path)?;
+    Ok(compare(original, current))
+}
+
+/// Compares `current` against the pristine source `original`. Returns `None`
+/// if `original` declares no diff budget.
+pub fn compare(original: String, current: String) -> Option<BudgetReport> {
+    let budget = Budget::parse(&original)?;
+    let usage = {
+        let original = significant_lines(&original);
+        let current = significant_lines(&current);
+        budget.usage(&diff::diff(&original, &current))
+    };
+
+    Some(BudgetReport {
+        budget,
+        usage,
+        original,
+        current,
+    })
+}
+
+/// Checks an exercise's diff budget, printing the diff if it is exceeded.
+/// Exercises whose pristine version cannot be found are let through.
+pub fn within_budget(exercise: &Exercise) -> bool {
+    match check(exercise) {
+        Ok(Some(report)) if !report.within() => {
+            print_over(exercise, &report);
+            false
+        }
+        Ok(_) => true,
+        Err(e) => {
+            warn!("Skipping the diff budget of {}: {}", exercise, e);
+            true
+        }
+    }
+}
+
+/// Prints how far over its diff budget `exercise` is, and its changes.
+pub fn print_over(exercise: &Exercise, report: &BudgetReport) {
+    warn!(
+        "{} is over its diff budget! Allowed: {}, used: {}. Your changes:",
+        exercise,
+        report.budget,
+        report.budget.describe(&report.usage)
+    );
+    report.print_diff();
+    println!();
+}
+
+/// Lines that count towards the budget: blank lines, comment-only lines and
+/// trailing whitespace are ignored.
+fn significant_lines(source: &str) -> Vec<&str> {
+    source
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty() && !line.trim_start().starts_with("//"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage_of(budget: &Budget, original: &str, current: &str) -> Usage {
+        let original = significant_lines(original);
+        let current = significant_lines(current);
+        budget.usage(&diff::diff(&original, &current))
+    }
+
+    #[test]
+    fn parses_every_header_style() {
+        let lines = |n| Budget {
+            lines: Some(n),
+            ..Budget::default()
+        };
+        assert_eq!(
+            Budget::parse("// FIXME: Make me compile! Diff budget: 1 line."),
+            Some(lines(1))
+        );
+        assert_eq!(
+            Budget::parse("// FIXME: Make me compile! Diff budget: 3 lines."),
+            Some(lines(3))
+        );
+        assert_eq!(
+            Budget::parse("// FIXME: Make me pass! Diff budget: 25 lines."),
+            Some(lines(25))
+        );
+        assert_eq!(
+            Budget::parse("// FIXME: Make me compile! Diff budget: 1 character."),
+            Some(Budget {
+                characters: Some(1),
+                ..Budget::default()
+            })
+        );
+        assert_eq!(
+            Budget::parse(
+                "// FIXME: Make me compile. Diff budget: 12 line additions and 2 characters."
+            ),
+            Some(Budget {
+                lines: None,
+                additions: Some(12),
+                characters: Some(2),
+            })
+        );
+    }
+
+    #[test]
+    fn finds_the_header_on_any_line() {
+        let source = "// A comment first.\n\n// FIXME: Make me pass! Diff budget: 2 lines.\n\nfn main() {}\n";
+        assert_eq!(
+            Budget::parse(source),
+            Some(Budget {
+                lines: Some(2),
+                ..Budget::default()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_missing_or_malformed_budgets() {
+        assert_eq!(
+            Budget::parse("// FIXME: Make me compile!\nfn main() {}"),
+            None
+        );
+        assert_eq!(Budget::parse("// Diff budget: some lines."), None);
+        assert_eq!(Budget::parse("// Diff budget: 2 words."), None);
+        assert_eq!(
+            Budget::parse("// Diff budget: 2 lines or 3 characters."),
+            None
+        );
+    }
+
+    #[test]
+    fn displays_like_the_header() {
+        for spec in &[
+            "1 line",
+            "3 lines",
+            "1 character",
+            "12 line additions and 2 characters",
+            "1 line addition and 1 character",
+        ] {
+            let budget = Budget::parse(&format!("// Diff budget: {}.", spec)).unwrap();
+            assert_eq!(budget.to_string(), *spec);
+        }
+    }
+
+    #[test]
+    fn counts_changed_lines() {
+        let budget = Budget::parse("// Diff budget: 2 lines.").unwrap();
+        let original = "fn main() {\n    let x = 5;\n    x = 6;\n}\n";
+
+        let usage = usage_of(&budget, original, original);
+        assert_eq!(usage.lines, 0);
+
+        let edited = "fn main() {\n    let mut x = 5;\n    x = 6;\n}\n";
+        assert_eq!(usage_of(&budget, original, edited).lines, 1);
+
+        let added = "fn main() {\n    let x = 5;\n    let y = 1;\n    let z = 2;\n    x = 6;\n}\n";
+        assert_eq!(usage_of(&budget, original, added).lines, 2);
+
+        let removed = "fn main() {\n    let x = 5;\n}\n";
+        assert_eq!(usage_of(&budget, original, removed).lines, 1);
+
+        // Replacing one line with two counts the larger side of the hunk.
+        let replaced = "fn main() {\n    let mut x = 5;\n    println!(\"{}\", x);\n    x = 6;\n}\n";
+        let usage = usage_of(&budget, original, replaced);
+        assert_eq!(usage.lines, 2);
+        assert!(budget.allows(&usage));
+    }
+
+    #[test]
+    fn ignores_blank_lines_comments_and_trailing_whitespace() {
+        let budget = Budget::parse("// Diff budget: 1 line.").unwrap();
+        let original = "fn main() {\n    let x = 5;\n}\n";
+        let current = "// Solved it!\nfn main() {   \n\n    // x is five.\n    let x = 5;\n}\n";
+        assert_eq!(usage_of(&budget, original, current), Usage::default());
+    }
+
+    #[test]
+    fn counts_characters_of_edits_and_removals() {
+        let budget = Budget::parse("// Diff budget: 1 character.").unwrap();
+        let original = "fn main() {\n    let x = 5\n}\n";
+
+        let usage = usage_of(&budget, original, "fn main() {\n    let x = 5;\n}\n");
+        assert_eq!(usage.characters, 1);
+        assert!(budget.allows(&usage));
+
+        let usage = usage_of(&budget, original, "fn main() {\n    let  x = 50;\n}\n");
+        assert_eq!(usage.characters, 3);
+        assert!(!budget.allows(&usage));
+
+        // A removed line costs its characters, leading indentation aside.
+        let usage = usage_of(&budget, original, "fn main() {\n}\n");
+        assert_eq!(usage.characters, "let x = 5".len());
+    }
+
+    #[test]
+    fn charges_added_lines_to_additions_if_budgeted() {
+        let budget = Budget::parse("// Diff budget: 1 line addition and 1 character.").unwrap();
+        let original = "fn main() {\n    let x = 5\n}\n";
+        let current = "use std::fmt;\nfn main() {\n    let x = 5;\n}\n";
+        let usage = usage_of(&budget, original, current);
+        assert_eq!(usage.additions, 1);
+        assert_eq!(usage.characters, 1);
+        assert!(budget.allows(&usage));
+        assert_eq!(budget.describe(&usage), "1 line addition and 1 character");
+
+        // Without an additions budget, an added line costs its characters.
+        let budget = Budget::parse("// Diff budget: 20 characters.").unwrap();
+        let usage = usage_of(&budget, original, current);
+        assert_eq!(usage.additions, 0);
+        assert_eq!(usage.characters, "use std::fmt;".len() + 1);
+    }
+
+    #[test]
+    fn compares_against_the_original_header() {
+        let original =
+            "// FIXME: Make me compile! Diff budget: 1 line.\nfn main() {\n    let x = 5\n}\n";
+        let current = "// FIXME: Make me compile! Diff budget: 99 lines.\nfn main() {\n    let x = 5;\n    let y = 6;\n}\n";
+        let report = compare(original.to_string(), current.to_string()).unwrap();
+        assert_eq!(report.budget.lines, Some(1));
+        assert_eq!(report.usage.lines, 2);
+        assert!(!report.within());
+
+        assert!(compare("fn main() {}".to_string(), String::new()).is_none());
+    }
+}
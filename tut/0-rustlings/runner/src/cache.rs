@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::exercise::Exercise;
+use crate::package;
+use crate::pristine;
+
+/// Where `verify` remembers what passed, relative to `tut/0-rustlings`.
+pub const CACHE_FILE: &str = ".rustlings-cache.toml";
+
+/// The fingerprints exercises had when they last passed `verify`, keyed by
+/// their `info.toml` name. An exercise whose fingerprint has not changed
+/// since is not checked again.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Cache {
+    #[serde(default)]
+    passed: BTreeMap<String, String>,
+}
+
+impl Cache {
+    /// Reads the cache file. A missing or unreadable cache is empty; it only
+    /// ever saves time.
+    pub fn load() -> Cache {
+        fs::read_to_string(CACHE_FILE)
+            .ok()
+            .and_then(|text| toml::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes the cache file.
+    pub fn save(&self) -> io::Result<()> {
+        let text =
+            toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(CACHE_FILE, text)
+    }
+
+    /// Returns whether `name` passed with this `fingerprint`.
+    pub fn passed(&self, name: &str, fingerprint: &str) -> bool {
+        self.passed.get(name).is_some_and(|f| f == fingerprint)
+    }
+
+    /// Records that `name` passed with this `fingerprint`.
+    pub fn insert(&mut self, name: &str, fingerprint: String) {
+        self.passed.insert(name.to_string(), fingerprint);
+    }
+}
+
+/// Describes the toolchain exercises are checked with, from `rustc -vV` and
+/// `cargo -V`, and when this runner was built, since a rebuilt runner may
+/// check exercises differently.
+pub fn toolchain() -> String {
+    let mut toolchain = String::new();
+    for (program, flag) in &[("rustc", "-vV"), ("cargo", "-V")] {
+        if let Ok(output) = Command::new(program).arg(flag).output() {
+            toolchain.push_str(&String::from_utf8_lossy(&output.stdout));
+        }
+    }
+    let built = env::current_exe()
+        .and_then(fs::metadata)
+        .and_then(|m| m.modified());
+    toolchain.push_str(&format!("{:?}", built.ok()));
+    toolchain
+}
+
+/// Hashes everything checking `exercise` depends on: its `info.toml` entry,
+/// every file of the exercise, the version it was handed out in, which its
+/// protected regions and diff budget are checked against, its fixtures, and
+/// the `toolchain`. Hidden tests only run when grading, which does not use the
+/// cache. Returns `None` if any of the files cannot be read, so that the
+/// exercise is checked and the failure shown.
+pub fn fingerprint(exercise: &Exercise, toolchain: &str) -> Option<String> {
+    let mut hasher = Sha256::new();
+    let mut add = |bytes: &[u8]| {
+        hasher.update((bytes.len() as u64).to_le_bytes());
+        hasher.update(bytes);
+    };
+    add(toolchain.as_bytes());
+    add(format!("{:?}", exercise).as_bytes());
+
+    if exercise.is_crate() {
+        for (path, contents) in package::read_tree(&exercise.path).ok()? {
+            add(path.to_string_lossy().as_bytes());
+            add(&contents);
+        }
+    } else {
+        add(&fs::read(&exercise.path).ok()?);
+    }
+    // Without git history the checks against the original are skipped, which
+    // the empty id stands for.
+    add(pristine::original_id(&exercise.path)
+        .unwrap_or_default()
+        .as_bytes());
+    for fixture in &exercise.fixtures {
+        add(&fs::read(&fixture.stdin).ok()?);
+    }
+    Some(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::{Path, PathBuf};
+    use std::process;
+
+    fn exercise(path: &Path) -> Exercise {
+        toml::from_str(&format!(
+            "name = \"a\"\npath = \"{}\"\nmode = \"test\"",
+            path.display()
+        ))
+        .unwrap()
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("rustlings_{}_cache_{}", process::id(), name));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn fingerprints_change_with_what_the_check_depends_on() {
+        let dir = scratch("file");
+        let path = dir.join("a.rs");
+        fs::write(&path, "fn main() {}\n").unwrap();
+        let exercise = exercise(&path);
+        let original = fingerprint(&exercise, "rustc 1.0").unwrap();
+        assert_eq!(fingerprint(&exercise, "rustc 1.0"), Some(original.clone()));
+
+        assert_ne!(fingerprint(&exercise, "rustc 2.0"), Some(original.clone()));
+        let mut edited = exercise.clone();
+        edited.limits.time += 1;
+        assert_ne!(fingerprint(&edited, "rustc 1.0"), Some(original.clone()));
+        fs::write(&path, "fn main() { }\n").unwrap();
+        assert_ne!(fingerprint(&exercise, "rustc 1.0"), Some(original));
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(fingerprint(&exercise, "rustc 1.0"), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn fingerprints_every_file_of_a_crate() {
+        let dir = scratch("crate");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join(package::MANIFEST), "[package]\n").unwrap();
+        fs::write(dir.join("src/lib.rs"), "mod a;\n").unwrap();
+        let exercise = exercise(&dir);
+        let original = fingerprint(&exercise, "").unwrap();
+
+        fs::write(dir.join("src/a.rs"), "").unwrap();
+        assert_ne!(fingerprint(&exercise, ""), Some(original));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn only_remembers_passes_with_the_same_fingerprint() {
+        let mut cache = Cache::default();
+        assert!(!cache.passed("a", "1"));
+        cache.insert("a", "1".to_string());
+        assert!(cache.passed("a", "1"));
+        assert!(!cache.passed("a", "2"));
+        assert!(!cache.passed("b", "1"));
+        cache.insert("a", "2".to_string());
+        assert!(!cache.passed("a", "1"));
+    }
+}
@@ -0,0 +1,209 @@
+use console::style;
+
+const CONTEXT: usize = 2;
+
+/// One line of a line-based diff between an old and a new text.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// A run of consecutive changed lines.
+#[derive(Debug, Default)]
+pub struct Hunk<'a> {
+    pub removed: Vec<&'a str>,
+    pub added: Vec<&'a str>,
+}
+
+/// Computes a longest-common-subsequence diff of `old` and `new`.
+pub fn diff<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    // lcs[i][j] is the LCS length of old[i..] and new[j..].
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(Line::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|l| Line::Removed(l)));
+    lines.extend(new[j..].iter().map(|l| Line::Added(l)));
+    lines
+}
+
+/// Groups the changed lines of a diff into hunks.
+pub fn hunks<'a>(lines: &[Line<'a>]) -> Vec<Hunk<'a>> {
+    let mut hunks = vec![];
+    let mut current = Hunk::default();
+    for line in lines {
+        match *line {
+            Line::Same(_) => {
+                if !current.removed.is_empty() || !current.added.is_empty() {
+                    hunks.push(std::mem::take(&mut current));
+                }
+            }
+            Line::Removed(l) => current.removed.push(l),
+            Line::Added(l) => current.added.push(l),
+        }
+    }
+    if !current.removed.is_empty() || !current.added.is_empty() {
+        hunks.push(current);
+    }
+    hunks
+}
+
+/// Returns the Levenshtein distance between `a` and `b` in characters.
+pub fn char_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + if ca == *cb { 0 } else { 1 };
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Prints the changed lines of a diff with a little surrounding context.
+pub fn print(lines: &[Line]) {
+    if lines.is_empty() {
+        return;
+    }
+    let changed: Vec<bool> = lines.iter().map(|l| !matches!(l, Line::Same(_))).collect();
+    let near_change = |i: usize| {
+        let first = i.saturating_sub(CONTEXT);
+        let last = (i + CONTEXT).min(lines.len() - 1);
+        changed[first..=last].iter().any(|&c| c)
+    };
+
+    let mut skipped = false;
+    for (i, line) in lines.iter().enumerate() {
+        if !near_change(i) {
+            skipped = true;
+            continue;
+        }
+        if skipped {
+            println!("{}", style("  ...").dim());
+            skipped = false;
+        }
+        match line {
+            Line::Same(l) => println!("  {}", l),
+            Line::Removed(l) => println!("{}", style(format!("- {}", l)).red()),
+            Line::Added(l) => println!("{}", style(format!("+ {}", l)).green()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<&str> {
+        text.lines().collect()
+    }
+
+    #[test]
+    fn same_texts_only_have_same_lines() {
+        let text = lines("a\nb\nc");
+        let diff = diff(&text, &text);
+        assert_eq!(
+            diff,
+            vec![Line::Same("a"), Line::Same("b"), Line::Same("c")]
+        );
+        assert!(hunks(&diff).is_empty());
+    }
+
+    #[test]
+    fn diffs_empty_texts() {
+        assert!(diff(&[], &[]).is_empty());
+        assert_eq!(
+            diff(&[], &lines("a\nb")),
+            vec![Line::Added("a"), Line::Added("b")]
+        );
+        assert_eq!(diff(&lines("a"), &[]), vec![Line::Removed("a")]);
+    }
+
+    #[test]
+    fn keeps_the_longest_common_subsequence() {
+        let diff = diff(&lines("a\nb\nc\nd"), &lines("a\nc\nd\ne"));
+        assert_eq!(
+            diff,
+            vec![
+                Line::Same("a"),
+                Line::Removed("b"),
+                Line::Same("c"),
+                Line::Same("d"),
+                Line::Added("e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_removals_before_additions_in_a_change() {
+        assert_eq!(
+            diff(&lines("a\nb\nc"), &lines("a\nx\nc")),
+            vec![
+                Line::Same("a"),
+                Line::Removed("b"),
+                Line::Added("x"),
+                Line::Same("c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_repeated_lines_apart() {
+        let diff = diff(&lines("}\n}"), &lines("}\nx\n}"));
+        assert_eq!(
+            diff,
+            vec![Line::Same("}"), Line::Added("x"), Line::Same("}")]
+        );
+    }
+
+    #[test]
+    fn groups_changes_into_hunks() {
+        let diff = diff(&lines("a\nb\nc\nd\ne"), &lines("a\nx\ny\nc\nd"));
+        let hunks = hunks(&diff);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].removed, vec!["b"]);
+        assert_eq!(hunks[0].added, vec!["x", "y"]);
+        assert_eq!(hunks[1].removed, vec!["e"]);
+        assert!(hunks[1].added.is_empty());
+    }
+
+    #[test]
+    fn measures_character_distance() {
+        assert_eq!(char_distance("", ""), 0);
+        assert_eq!(char_distance("abc", "abc"), 0);
+        assert_eq!(char_distance("", "abc"), 3);
+        assert_eq!(char_distance("abc", ""), 3);
+        assert_eq!(char_distance("let x = 5", "let x = 5;"), 1);
+        assert_eq!(char_distance("let x", "let mut x"), 4);
+        assert_eq!(char_distance("kitten", "sitting"), 3);
+        assert_eq!(char_distance("héllo", "hello"), 1);
+    }
+}
@@ -0,0 +1,254 @@
+use std::path::Path;
+use std::process::Command;
+
+use console::style;
+
+use crate::qemu;
+use crate::xbuild;
+
+/// The components `bin/setup.sh` adds to the pinned toolchain.
+const COMPONENTS: &[&str] = &["rust-src", "llvm-tools-preview", "clippy"];
+
+/// The cargo-xbuild release `bin/setup.sh` patches and installs.
+const XBUILD_VERSION: &str = "0.5.20";
+
+/// The QEMU commit `bin/build-qemu.sh` builds.
+const QEMU_COMMIT: &str = "9b4efa2ede5db24377405a21b218066b90fe2f0e";
+
+/// The linker tools the labs link kernels with, and the binary each
+/// provides.
+const LLVM_TOOLS: &[(&str, &str)] = &[("clang-8", "clang-8"), ("lld-8", "ld.lld-8")];
+
+/// Where `bin/setup.sh` checks out the tools it builds from source, and where
+/// `bin/build-qemu.sh` builds QEMU, relative to `tut/0-rustlings`.
+const DEP_DIR: &str = "../../.dep";
+const QEMU_DIR: &str = "../../ext/qemu";
+
+/// Why a check failed and the command that fixes it, to run from
+/// `tut/0-rustlings`.
+struct Gap {
+    problem: String,
+    fix: String,
+}
+
+impl Gap {
+    fn new(problem: impl Into<String>, fix: impl Into<String>) -> Gap {
+        Gap {
+            problem: problem.into(),
+            fix: fix.into(),
+        }
+    }
+}
+
+/// Checks the toolchain, its components, the cargo subcommands and the tools
+/// `bin/setup.sh` and `bin/build-qemu.sh` install, without going online.
+/// Prints a checklist with the command that fixes each gap, and returns
+/// whether everything is in place.
+pub fn doctor() -> bool {
+    let installed = output("cargo", &["install", "--list"]).unwrap_or_default();
+    let mut checks = vec![
+        ("rustup", rustup()),
+        ("toolchain", toolchain()),
+        ("default toolchain", default_toolchain()),
+    ];
+    for component in COMPONENTS {
+        checks.push((component, component_installed(component)));
+    }
+    checks.push(("cargo-xbuild", cargo_xbuild(&installed)));
+    checks.push(("cargo-binutils", cargo_binutils(&installed)));
+    for (package, binary) in LLVM_TOOLS {
+        checks.push((package, llvm_tool(package, binary)));
+    }
+    checks.push(("qemu", qemu()));
+
+    let mut gaps = 0;
+    for (what, result) in &checks {
+        match result {
+            Ok(found) => success!("{}: {}", what, found),
+            Err(gap) => {
+                gaps += 1;
+                warn!("{}: {}", what, gap.problem);
+                println!("    fix: {}", style(&gap.fix).bold());
+            }
+        }
+    }
+    println!();
+    if gaps == 0 {
+        success!("All {} checks passed.", checks.len());
+    } else {
+        warn!("{} of {} checks failed.", gaps, checks.len());
+    }
+    gaps == 0
+}
+
+fn rustup() -> Result<String, Gap> {
+    match output("rustup", &["--version"]) {
+        Some(version) => Ok(first_line(&version)),
+        None => Err(Gap::new(
+            "rustup is not installed or not on your PATH",
+            "../../bin/setup.sh",
+        )),
+    }
+}
+
+fn toolchain() -> Result<String, Gap> {
+    let toolchains = output("rustup", &["toolchain", "list"]).unwrap_or_default();
+    if toolchains
+        .lines()
+        .any(|line| line.starts_with(xbuild::TOOLCHAIN))
+    {
+        Ok(format!("{} is installed", xbuild::TOOLCHAIN))
+    } else {
+        Err(Gap::new(
+            format!("{} is not installed", xbuild::TOOLCHAIN),
+            format!("rustup toolchain install {}", xbuild::TOOLCHAIN),
+        ))
+    }
+}
+
+/// Checks that the pinned toolchain is the one exercises and labs compile
+/// with. The runner's own `rust-toolchain.toml` does not apply here, since
+/// the runner is run from `tut/0-rustlings`.
+fn default_toolchain() -> Result<String, Gap> {
+    let active = output("rustup", &["show", "active-toolchain"]).unwrap_or_default();
+    let active = active.split_whitespace().next().unwrap_or("none");
+    if active.starts_with(xbuild::TOOLCHAIN) {
+        Ok(format!("{} is active", active))
+    } else {
+        Err(Gap::new(
+            format!("{} is active instead of {}", active, xbuild::TOOLCHAIN),
+            format!("rustup default {}", xbuild::TOOLCHAIN),
+        ))
+    }
+}
+
+fn component_installed(component: &str) -> Result<String, Gap> {
+    let installed = output(
+        "rustup",
+        &[
+            "component",
+            "list",
+            "--installed",
+            "--toolchain",
+            xbuild::TOOLCHAIN,
+        ],
+    )
+    .unwrap_or_default();
+    // Newer rustup lists `llvm-tools-preview` as `llvm-tools`.
+    let prefix = component.trim_end_matches("-preview");
+    if installed.lines().any(|line| line.starts_with(prefix)) {
+        Ok(format!("installed for {}", xbuild::TOOLCHAIN))
+    } else {
+        Err(Gap::new(
+            format!("not installed for {}", xbuild::TOOLCHAIN),
+            format!(
+                "rustup component add {} --toolchain {}",
+                component,
+                xbuild::TOOLCHAIN
+            ),
+        ))
+    }
+}
+
+/// Checks for the cargo-xbuild release `bin/setup.sh` builds from its patched
+/// checkout. A release from crates.io lacks the patch.
+fn cargo_xbuild(installed: &str) -> Result<String, Gap> {
+    let checkout = Path::new(DEP_DIR).join("cargo-xbuild");
+    let fix = if checkout.is_dir() {
+        format!("cargo install -f --path {} --locked", checkout.display())
+    } else {
+        "../../bin/setup.sh".to_string()
+    };
+    match installed_package(installed, "cargo-xbuild") {
+        None => Err(Gap::new("not installed", fix)),
+        Some((version, _)) if version != XBUILD_VERSION => Err(Gap::new(
+            format!(
+                "version {} is installed instead of {}",
+                version, XBUILD_VERSION
+            ),
+            fix,
+        )),
+        Some((_, None)) => Err(Gap::new(
+            "installed from crates.io, without the patch `bin/setup.sh` applies",
+            fix,
+        )),
+        Some((version, Some(_))) => Ok(format!("version {}, patched", version)),
+    }
+}
+
+fn cargo_binutils(installed: &str) -> Result<String, Gap> {
+    let checkout = Path::new(DEP_DIR).join("cargo-binutils");
+    let fix = if checkout.is_dir() {
+        format!("cargo install -f --path {} --locked", checkout.display())
+    } else {
+        "../../bin/setup.sh".to_string()
+    };
+    match installed_package(installed, "cargo-binutils") {
+        Some((version, _)) => Ok(format!("version {}", version)),
+        None => Err(Gap::new("not installed", fix)),
+    }
+}
+
+fn llvm_tool(package: &str, binary: &str) -> Result<String, Gap> {
+    match output(binary, &["--version"]) {
+        Some(version) => Ok(first_line(&version)),
+        None => Err(Gap::new(
+            format!("`{}` is not installed or not on your PATH", binary),
+            format!("sudo apt install -y {}", package),
+        )),
+    }
+}
+
+/// Checks for a QEMU that runs, and if it was built by `bin/build-qemu.sh`,
+/// that it was built from the pinned commit.
+fn qemu() -> Result<String, Gap> {
+    let binary = qemu::find_qemu().ok_or_else(|| {
+        Gap::new(
+            "qemu-system-aarch64 was not found",
+            "../../bin/build-qemu.sh",
+        )
+    })?;
+    let version = output(&binary.to_string_lossy(), &["--version"]).ok_or_else(|| {
+        Gap::new(
+            format!("{} does not run", binary.display()),
+            format!("rm -rf {} && ../../bin/build-qemu.sh", QEMU_DIR),
+        )
+    })?;
+    if Path::new(QEMU_DIR).join(".git").exists() {
+        let head = output("git", &["-C", QEMU_DIR, "rev-parse", "HEAD"]).unwrap_or_default();
+        if head.trim() != QEMU_COMMIT {
+            return Err(Gap::new(
+                format!("{} is not checked out at {}", QEMU_DIR, QEMU_COMMIT),
+                format!("rm -rf {} && ../../bin/build-qemu.sh", QEMU_DIR),
+            ));
+        }
+    }
+    Ok(first_line(&version))
+}
+
+/// Finds `package` in the output of `cargo install --list`, returning its
+/// version and the path it was installed from, if it was not a registry.
+fn installed_package<'a>(installed: &'a str, package: &str) -> Option<(&'a str, Option<&'a str>)> {
+    let prefix = format!("{} v", package);
+    let line = installed.lines().find(|line| line.starts_with(&prefix))?;
+    let rest = line[prefix.len()..].trim_end_matches(':');
+    let version = rest.split_whitespace().next()?;
+    let path = rest
+        .find('(')
+        .map(|start| rest[start + 1..].trim_end_matches(')'));
+    Some((version, path))
+}
+
+/// Runs `program` and returns its stdout, or `None` if it cannot be run or
+/// fails.
+fn output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn first_line(text: &str) -> String {
+    text.lines().next().unwrap_or_default().trim().to_string()
+}
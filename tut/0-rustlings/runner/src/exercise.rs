@@ -0,0 +1,665 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output};
+use std::ptr;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::explain;
+use crate::package;
+use crate::qemu;
+use crate::sandbox::{self, Violation};
+use crate::xbuild;
+
+const RUSTC_COLOR_ARGS: &[&str] = &["--color", "always"];
+const RUSTC_EDITION_ARGS: &[&str] = &["--edition", "2018"];
+/// Lets test output reach stdout, where it counts against the output limit,
+/// without tests interleaving it.
+const TEST_HARNESS_ARGS: &[&str] = &["--test-threads=1", "--nocapture"];
+const I_AM_DONE_REGEX: &str = r"(?m)^\s*///?\s*I\s+AM\s+NOT\s+DONE";
+const CONTEXT: usize = 2;
+
+/// How an exercise is checked.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// The exercise passes once it compiles.
+    Compile,
+    /// The exercise passes once it compiles with `--test` and every test passes.
+    Test,
+    /// The exercise passes once its binary prints `expected_output` and exits
+    /// with `exit_code`.
+    Run,
+    /// The exercise passes once it compiles without clippy warnings at
+    /// `lint_level` or from any of `lints`.
+    Clippy,
+    /// The exercise passes once it cross-compiles as a `no_std` library for
+    /// `target` with `cargo xbuild` on the pinned toolchain.
+    Xbuild,
+    /// The exercise passes once it boots on QEMU inside a bare-metal stub,
+    /// prints `expected_output` to the serial port and exits with `exit_code`.
+    Qemu,
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mode::Compile => write!(f, "compile"),
+            Mode::Test => write!(f, "test"),
+            Mode::Run => write!(f, "run"),
+            Mode::Clippy => write!(f, "clippy"),
+            Mode::Xbuild => write!(f, "xbuild"),
+            Mode::Qemu => write!(f, "qemu"),
+        }
+    }
+}
+
+/// The contents of `info.toml`.
+#[derive(Deserialize, Debug)]
+pub struct ExerciseList {
+    pub exercises: Vec<Exercise>,
+}
+
+impl ExerciseList {
+    /// Reads and parses the exercise list at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<ExerciseList> {
+        let text = fs::read_to_string(path)?;
+        let list: ExerciseList =
+            toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        list.check_requirements()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(list)
+    }
+
+    /// Returns the exercise named `name`, if any.
+    pub fn find(&self, name: &str) -> Option<&Exercise> {
+        self.exercises.iter().find(|e| e.name == name)
+    }
+
+    /// Groups exercises by section, in order of first appearance.
+    pub fn sections(&self) -> Vec<(String, Vec<&Exercise>)> {
+        let mut sections: Vec<(String, Vec<&Exercise>)> = vec![];
+        for exercise in &self.exercises {
+            let section = exercise.section();
+            match sections.iter_mut().find(|(name, _)| *name == section) {
+                Some((_, exercises)) => exercises.push(exercise),
+                None => sections.push((section, vec![exercise])),
+            }
+        }
+        sections
+    }
+
+    /// Groups exercises by topic, in order of first appearance. An exercise
+    /// appears once under each of its topics.
+    pub fn topics(&self) -> Vec<(String, Vec<&Exercise>)> {
+        let mut topics: Vec<(String, Vec<&Exercise>)> = vec![];
+        for exercise in &self.exercises {
+            for topic in exercise.topics() {
+                match topics.iter_mut().find(|(name, _)| *name == topic) {
+                    Some((_, exercises)) => exercises.push(exercise),
+                    None => topics.push((topic, vec![exercise])),
+                }
+            }
+        }
+        topics
+    }
+
+    /// Returns the exercises `exercise` requires: its `requires`, or else the
+    /// entry before it. Unknown names are skipped.
+    pub fn requirements(&self, exercise: &Exercise) -> Vec<&Exercise> {
+        match &exercise.requires {
+            Some(names) => names.iter().filter_map(|name| self.find(name)).collect(),
+            None => {
+                let position = self.exercises.iter().position(|e| e.name == exercise.name);
+                match position {
+                    Some(i) if i > 0 => vec![&self.exercises[i - 1]],
+                    _ => vec![],
+                }
+            }
+        }
+    }
+
+    /// Returns every exercise after everything it requires, otherwise in
+    /// `info.toml` order.
+    pub fn ordered(&self) -> Vec<&Exercise> {
+        self.try_order()
+            .expect("requirements were checked for cycles on load")
+    }
+
+    /// Returns the exercises with `topic` and everything they require,
+    /// directly or not, in the order of `ordered`.
+    pub fn path(&self, topic: &str) -> Vec<&Exercise> {
+        let mut needed: Vec<&Exercise> = self
+            .exercises
+            .iter()
+            .filter(|e| e.topics().iter().any(|t| t == topic))
+            .collect();
+        let mut i = 0;
+        while i < needed.len() {
+            for requirement in self.requirements(needed[i]) {
+                if !needed.iter().any(|e| ptr::eq(*e, requirement)) {
+                    needed.push(requirement);
+                }
+            }
+            i += 1;
+        }
+        self.ordered()
+            .into_iter()
+            .filter(|e| needed.iter().any(|n| ptr::eq(*n, *e)))
+            .collect()
+    }
+
+    /// Returns an error if an exercise requires one that does not exist, or
+    /// if requirements form a cycle.
+    pub fn check_requirements(&self) -> Result<(), String> {
+        for exercise in &self.exercises {
+            for name in exercise.requires.iter().flatten() {
+                if self.find(name).is_none() {
+                    return Err(format!(
+                        "`{}` requires unknown exercise `{}`",
+                        exercise.name, name
+                    ));
+                }
+            }
+        }
+        match self.try_order() {
+            Ok(_) => Ok(()),
+            Err(stuck) => {
+                // Every stuck exercise waits on another stuck one, so following
+                // those requirements must eventually come back around.
+                let mut chain = vec![stuck[0]];
+                let start = loop {
+                    let last = chain[chain.len() - 1];
+                    let next = self
+                        .requirements(last)
+                        .into_iter()
+                        .find(|r| stuck.iter().any(|e| ptr::eq(*e, *r)))
+                        .expect("a stuck exercise waits on another");
+                    if let Some(i) = chain.iter().position(|e| ptr::eq(*e, next)) {
+                        chain.push(next);
+                        break i;
+                    }
+                    chain.push(next);
+                };
+                let names: Vec<&str> = chain[start..].iter().map(|e| e.name.as_str()).collect();
+                Err(format!(
+                    "requirements form a cycle: {}",
+                    names.join(" requires ")
+                ))
+            }
+        }
+    }
+
+    /// Orders exercises as `ordered` does, or returns the ones that cannot
+    /// be ordered because their requirements form a cycle.
+    fn try_order(&self) -> Result<Vec<&Exercise>, Vec<&Exercise>> {
+        let mut ordered: Vec<&Exercise> = vec![];
+        let mut left: Vec<&Exercise> = self.exercises.iter().collect();
+        while !left.is_empty() {
+            let next = left.iter().position(|exercise| {
+                self.requirements(exercise)
+                    .iter()
+                    .all(|r| ordered.iter().any(|e| ptr::eq(*e, *r)))
+            });
+            match next {
+                Some(i) => ordered.push(left.remove(i)),
+                None => return Err(left),
+            }
+        }
+        Ok(ordered)
+    }
+}
+
+/// A single `[[exercises]]` entry.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Exercise {
+    pub name: String,
+    pub path: PathBuf,
+    pub mode: Mode,
+    /// A single hint. Superseded by `hints` if both are given.
+    #[serde(default)]
+    pub hint: String,
+    /// Hints from a gentle nudge to the answer, revealed one per
+    /// `rustlings hint`.
+    #[serde(default)]
+    pub hints: Vec<String>,
+    /// What a `run` exercise must print to stdout. Unchecked if absent.
+    #[serde(default)]
+    pub expected_output: Option<String>,
+    /// The status a `run` exercise must exit with.
+    #[serde(default)]
+    pub exit_code: i32,
+    /// Stdin fixtures for a `run` exercise. If present, the binary is run
+    /// once per fixture instead of once with `expected_output`.
+    #[serde(default)]
+    pub fixtures: Vec<Fixture>,
+    /// The clippy lint group a `clippy` exercise is checked at, e.g. `all` or
+    /// `pedantic`. Defaults to `all` unless `lints` is given.
+    #[serde(default)]
+    pub lint_level: Option<String>,
+    /// Individual clippy lints a `clippy` exercise is checked against, e.g.
+    /// `needless_return`.
+    #[serde(default)]
+    pub lints: Vec<String>,
+    /// The target an `xbuild` exercise is compiled for. Defaults to
+    /// `aarch64-unknown-none`.
+    #[serde(default)]
+    pub target: Option<String>,
+    /// A file of `#[test]` functions that grading splices into the exercise
+    /// and reports apart from its visible tests, relative to the directory
+    /// named by `$RUSTLINGS_HIDDEN_TESTS` or `verify --hidden-tests`.
+    #[serde(default)]
+    pub hidden_tests: Option<PathBuf>,
+    /// The exercises that must be done before this one. Defaults to the
+    /// entry before it in `info.toml`; `[]` makes it available from the start.
+    #[serde(default)]
+    pub requires: Option<Vec<String>>,
+    /// What the exercise is about, e.g. `lifetimes`. Defaults to its section.
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// Lesson-specific explanations shown next to the compiler errors they
+    /// match.
+    #[serde(default)]
+    pub explanations: Vec<Explanation>,
+    /// What the compiled exercise may use before it is stopped. A `qemu`
+    /// exercise is only held to the time and output limits.
+    #[serde(default)]
+    pub limits: Limits,
+    /// Lines the student's version must keep, for rules that the marker
+    /// comments of `protect` cannot express.
+    #[serde(default)]
+    pub required: Vec<Required>,
+}
+
+/// A pattern some line of an exercise must match, and why.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Required {
+    pub pattern: String,
+    pub reason: String,
+}
+
+/// A file fed to a `run` exercise's stdin and the result it must produce.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Fixture {
+    pub stdin: PathBuf,
+    #[serde(default)]
+    pub expected_output: Option<String>,
+    #[serde(default)]
+    pub exit_code: i32,
+}
+
+/// An explanation tied to a compiler error an exercise is meant to provoke.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Explanation {
+    /// The diagnostic code, e.g. `E0384`, or a lint such as `unused_mut`.
+    pub code: String,
+    /// A regex the diagnostic's message must match as well, if given.
+    #[serde(default)]
+    pub message: Option<String>,
+    pub text: String,
+}
+
+/// What a compiled exercise may use each time it runs, so that a loop or a
+/// runaway allocation fails the exercise instead of hanging the runner. Any
+/// limit left out of an `info.toml` entry keeps its default.
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(default, deny_unknown_fields)]
+pub struct Limits {
+    /// Seconds of wall-clock time.
+    pub time: u64,
+    /// Seconds of CPU time.
+    pub cpu: Option<u64>,
+    /// Mebibytes of memory.
+    pub memory: Option<u64>,
+    /// Bytes of stdout and of stderr. Output beyond them is dropped and fails
+    /// the run.
+    pub output: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            time: 10,
+            cpu: Some(5),
+            memory: Some(1024),
+            output: 1 << 20,
+        }
+    }
+}
+
+/// Whether the student has removed the `I AM NOT DONE` marker.
+#[derive(Debug, PartialEq, Eq)]
+pub enum State {
+    Done,
+    /// The file still holding the marker, and the lines around it.
+    Pending(PathBuf, Vec<ContextLine>),
+}
+
+/// A line around the `I AM NOT DONE` marker, shown to nudge the student.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ContextLine {
+    pub line: String,
+    pub number: usize,
+    pub important: bool,
+}
+
+/// Captured output of `rustc` or of a compiled exercise.
+#[derive(Debug)]
+pub struct ExerciseOutput {
+    pub stdout: String,
+    pub stderr: String,
+    /// The exit code, or `None` if the process was killed by a signal or
+    /// could not be started.
+    pub status: Option<i32>,
+    /// The limit the process was stopped for exceeding, if any.
+    pub violation: Option<Violation>,
+}
+
+impl From<Output> for ExerciseOutput {
+    fn from(output: Output) -> ExerciseOutput {
+        ExerciseOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            status: output.status.code(),
+            violation: None,
+        }
+    }
+}
+
+impl From<io::Error> for ExerciseOutput {
+    fn from(error: io::Error) -> ExerciseOutput {
+        ExerciseOutput {
+            stdout: String::new(),
+            stderr: error.to_string(),
+            status: None,
+            violation: None,
+        }
+    }
+}
+
+/// A binary built from an exercise, or a package built from a crate
+/// exercise. The binary, and the scratch directory it was built in if any,
+/// are deleted on drop.
+pub struct CompiledExercise {
+    artifact: Artifact,
+    scratch: Option<PathBuf>,
+    limits: Limits,
+    /// Whether the binary is a test harness.
+    harness: bool,
+}
+
+enum Artifact {
+    Binary(PathBuf),
+    /// The manifest of a package whose tests run through `cargo test`.
+    Package(PathBuf),
+}
+
+impl CompiledExercise {
+    /// The path of the built artifact, or the manifest of a built package.
+    pub fn binary(&self) -> &Path {
+        match &self.artifact {
+            Artifact::Binary(binary) => binary,
+            Artifact::Package(manifest) => manifest,
+        }
+    }
+
+    /// Wraps an artifact built inside the scratch directory `scratch`.
+    pub fn in_scratch(binary: PathBuf, scratch: PathBuf) -> CompiledExercise {
+        CompiledExercise {
+            artifact: Artifact::Binary(binary),
+            scratch: Some(scratch),
+            limits: Limits::default(),
+            harness: false,
+        }
+    }
+
+    /// Wraps a package copied to and built inside the scratch directory
+    /// `scratch`.
+    pub fn package(manifest: PathBuf, scratch: PathBuf) -> CompiledExercise {
+        CompiledExercise {
+            artifact: Artifact::Package(manifest),
+            scratch: Some(scratch),
+            limits: Limits::default(),
+            harness: false,
+        }
+    }
+
+    /// Returns the command that runs the compiled binary, or `cargo test` for
+    /// a package. Arguments added to it go to the binary or the test harness.
+    pub fn command(&self) -> Command {
+        let mut cmd = match &self.artifact {
+            Artifact::Binary(binary) => Command::new(binary),
+            Artifact::Package(manifest) => {
+                let mut cmd = package::cargo("test", manifest);
+                cmd.arg("--");
+                cmd
+            }
+        };
+        if self.harness {
+            cmd.args(TEST_HARNESS_ARGS);
+        }
+        cmd
+    }
+
+    /// Runs the compiled binary under the exercise's limits. For `test`
+    /// exercises this is the test harness.
+    pub fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
+        self.run_sandboxed(None)
+    }
+
+    /// Runs the compiled binary under the exercise's limits with `input` fed
+    /// to its stdin.
+    pub fn run_with_input(&self, input: &[u8]) -> Result<ExerciseOutput, ExerciseOutput> {
+        self.run_sandboxed(Some(input))
+    }
+
+    fn run_sandboxed(&self, input: Option<&[u8]>) -> Result<ExerciseOutput, ExerciseOutput> {
+        let (output, violation) = sandbox::output(&self.command(), &self.limits, input)?;
+        let success = output.status.success() && violation.is_none();
+        let mut output = ExerciseOutput::from(output);
+        output.violation = violation;
+        if success {
+            Ok(output)
+        } else {
+            Err(output)
+        }
+    }
+}
+
+impl Drop for CompiledExercise {
+    fn drop(&mut self) {
+        if let Artifact::Binary(binary) = &self.artifact {
+            let _ = fs::remove_file(binary);
+        }
+        if let Some(scratch) = &self.scratch {
+            let _ = fs::remove_dir_all(scratch);
+        }
+    }
+}
+
+impl Exercise {
+    /// Compiles the exercise with `rustc`, adding `--test` for `test`
+    /// exercises. `clippy` exercises are compiled with `clippy-driver`
+    /// instead, with their lints denied, and `xbuild` and `qemu` exercises
+    /// are cross-compiled for bare metal. Crate exercises are built with
+    /// `cargo`. If the exercise has `explanations`, they are worked into the
+    /// compiler output on failure.
+    pub fn compile(&self) -> Result<CompiledExercise, ExerciseOutput> {
+        let mut compiled = match self.mode {
+            Mode::Xbuild => xbuild::build_lib(self),
+            Mode::Qemu => qemu::build_image(self),
+            _ if self.is_crate() => package::build(self),
+            _ => self.rustc(),
+        }?;
+        compiled.limits = self.limits;
+        compiled.harness = self.mode == Mode::Test;
+        Ok(compiled)
+    }
+
+    /// Compiles a single-file exercise with `rustc`, or `clippy-driver` for
+    /// `clippy` exercises.
+    fn rustc(&self) -> Result<CompiledExercise, ExerciseOutput> {
+        let binary = self.temp_binary();
+        let mut cmd = match self.mode {
+            Mode::Clippy => {
+                // Outside of `cargo clippy`, the pinned nightly's driver only
+                // registers its lints when it thinks it is running clippy's
+                // own tests; without this `-D clippy::all` is an unknown lint.
+                let mut cmd = Command::new("clippy-driver");
+                cmd.args(self.clippy_args()).env("CLIPPY_TESTS", "true");
+                cmd
+            }
+            _ => Command::new("rustc"),
+        };
+        cmd.arg(&self.path).arg("-o").arg(&binary);
+        if self.explanations.is_empty() {
+            cmd.args(RUSTC_COLOR_ARGS);
+        } else {
+            cmd.args(explain::RUSTC_JSON_ARGS);
+        }
+        cmd.args(RUSTC_EDITION_ARGS);
+        if self.mode == Mode::Test {
+            cmd.arg("--test");
+        }
+
+        let output = cmd.output()?;
+        if output.status.success() {
+            Ok(CompiledExercise {
+                artifact: Artifact::Binary(binary),
+                scratch: None,
+                limits: Limits::default(),
+                harness: false,
+            })
+        } else {
+            let _ = fs::remove_file(&binary);
+            let mut output = ExerciseOutput::from(output);
+            if !self.explanations.is_empty() {
+                output.stderr = explain::render(&output.stderr, &self.explanations);
+            }
+            Err(output)
+        }
+    }
+
+    /// Reads the exercise's files and reports whether one is still marked as
+    /// not done.
+    pub fn state(&self) -> io::Result<State> {
+        let re = Regex::new(I_AM_DONE_REGEX).unwrap();
+        let mut marked = None;
+        for file in self.source_files()? {
+            let source = fs::read_to_string(&file)?;
+            if re.is_match(&source) {
+                marked = Some((file, source));
+                break;
+            }
+        }
+        let (file, source) = match marked {
+            Some(marked) => marked,
+            None => return Ok(State::Done),
+        };
+
+        let lines: Vec<&str> = source.lines().collect();
+        let marker = lines
+            .iter()
+            .position(|line| is_done_marker(line))
+            .expect("marker matched the file but no single line");
+        let first = marker.saturating_sub(CONTEXT);
+        let last = (marker + CONTEXT).min(lines.len() - 1);
+        let context = (first..=last)
+            .map(|i| ContextLine {
+                line: lines[i].to_string(),
+                number: i + 1,
+                important: i == marker,
+            })
+            .collect();
+        Ok(State::Pending(file, context))
+    }
+
+    fn clippy_args(&self) -> Vec<String> {
+        let level = match &self.lint_level {
+            Some(level) => Some(level.as_str()),
+            None if self.lints.is_empty() => Some("all"),
+            None => None,
+        };
+        level
+            .into_iter()
+            .chain(self.lints.iter().map(String::as_str))
+            .flat_map(|lint| {
+                let lint = lint.trim_start_matches("clippy::");
+                vec!["-D".to_string(), format!("clippy::{}", lint)]
+            })
+            .collect()
+    }
+
+    /// The hint levels of the exercise, in the order they are revealed.
+    pub fn hints(&self) -> Vec<&str> {
+        if self.hints.is_empty() {
+            Some(self.hint.as_str())
+                .filter(|hint| !hint.is_empty())
+                .into_iter()
+                .collect()
+        } else {
+            self.hints.iter().map(String::as_str).collect()
+        }
+    }
+
+    /// The directory the exercise lives in under `exercises/`, e.g. `cs140e`.
+    /// The tests at the top level are grouped as `quizzes`.
+    pub fn section(&self) -> String {
+        match self.path.parent().and_then(|dir| dir.file_name()) {
+            Some(dir) if dir != "exercises" => dir.to_string_lossy().into_owned(),
+            _ => "quizzes".to_string(),
+        }
+    }
+
+    /// Returns whether the exercise is a crate: a directory with a
+    /// `Cargo.toml` rather than a single file.
+    pub fn is_crate(&self) -> bool {
+        self.path.is_dir()
+    }
+
+    /// The Rust files of the exercise: its file, or every `.rs` file of a
+    /// crate outside `target/`, sorted.
+    pub fn source_files(&self) -> io::Result<Vec<PathBuf>> {
+        if !self.is_crate() {
+            return Ok(vec![self.path.clone()]);
+        }
+        let mut files = vec![];
+        package::rust_files(&self.path, &mut files)?;
+        files.sort();
+        Ok(files)
+    }
+
+    /// The topics of the exercise, or its section if none are given.
+    pub fn topics(&self) -> Vec<String> {
+        if self.topics.is_empty() {
+            vec![self.section()]
+        } else {
+            self.topics.clone()
+        }
+    }
+
+    /// A per-process scratch path for building this exercise.
+    pub fn temp_binary(&self) -> PathBuf {
+        let file = format!("rustlings_{}_{}", process::id(), self.name);
+        env::temp_dir().join(file)
+    }
+}
+
+/// Returns whether `line` is an `I AM NOT DONE` marker.
+pub fn is_done_marker(line: &str) -> bool {
+    Regex::new(I_AM_DONE_REGEX).unwrap().is_match(line)
+}
+
+impl fmt::Display for Exercise {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.path.display())
+    }
+}
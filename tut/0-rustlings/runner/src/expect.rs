@@ -0,0 +1,256 @@
+use std::fmt::Display;
+use std::fs;
+
+use crate::diff;
+use crate::exercise::{CompiledExercise, Exercise, ExerciseOutput, Mode};
+use crate::qemu;
+
+/// What a single run of an exercise binary must produce.
+pub struct Expected<'a> {
+    pub stdout: Option<&'a str>,
+    pub exit_code: i32,
+}
+
+/// A single run of an exercise binary and what it should have produced.
+pub struct Run<'a> {
+    /// Names the run in messages, e.g. `exercises/foo.rs < fixtures/a.txt`.
+    pub label: String,
+    pub expected: Expected<'a>,
+    pub output: ExerciseOutput,
+}
+
+/// Returns whether `actual` is the output `expected`, ignoring trailing
+/// whitespace on each line and trailing blank lines.
+pub fn stdout_matches(expected: &str, actual: &str) -> bool {
+    normalize(expected) == normalize(actual)
+}
+
+/// Describes each way `output` differs from what was expected.
+pub fn differences(expected: &Expected, output: &ExerciseOutput) -> Vec<String> {
+    let mut differences: Vec<String> = exit_mismatch(expected, output).into_iter().collect();
+    if let Some(stdout) = expected.stdout {
+        if !stdout_matches(stdout, &output.stdout) {
+            differences.push("printed unexpected output".to_string());
+        }
+    }
+    differences
+}
+
+/// Compares the output of one run with what was expected, printing whatever
+/// differs.
+pub fn output_matches(what: &dyn Display, expected: &Expected, output: &ExerciseOutput) -> bool {
+    if let Some(violation) = output.violation {
+        warn!("{} {} and was stopped.", what, violation);
+        return false;
+    }
+
+    let mut matches = true;
+    if let Some(mismatch) = exit_mismatch(expected, output) {
+        warn!("{} {}.", what, mismatch);
+        if !output.stderr.is_empty() {
+            println!("{}", output.stderr);
+        }
+        matches = false;
+    }
+
+    if let Some(stdout) = expected.stdout {
+        if !stdout_matches(stdout, &output.stdout) {
+            warn!("{} printed unexpected output (- expected, + actual):", what);
+            diff::print(&diff::diff(&normalize(stdout), &normalize(&output.stdout)));
+            println!();
+            matches = false;
+        }
+    }
+
+    matches
+}
+
+/// Runs a compiled `run` exercise once, or once per stdin fixture. `qemu`
+/// exercises are booted once.
+pub fn runs<'a>(exercise: &'a Exercise, compiled: &CompiledExercise) -> Vec<Run<'a>> {
+    if exercise.fixtures.is_empty() {
+        let output = match exercise.mode {
+            Mode::Qemu => qemu::boot(exercise, compiled.binary()),
+            _ => match compiled.run() {
+                Ok(output) | Err(output) => output,
+            },
+        };
+        let expected = Expected {
+            stdout: exercise.expected_output.as_deref(),
+            exit_code: exercise.exit_code,
+        };
+        return vec![Run {
+            label: exercise.to_string(),
+            expected,
+            output,
+        }];
+    }
+
+    exercise
+        .fixtures
+        .iter()
+        .map(|fixture| {
+            let output = match fs::read(&fixture.stdin) {
+                Ok(input) => match compiled.run_with_input(&input) {
+                    Ok(output) | Err(output) => output,
+                },
+                Err(e) => ExerciseOutput::from(e),
+            };
+            let expected = Expected {
+                stdout: fixture.expected_output.as_deref(),
+                exit_code: fixture.exit_code,
+            };
+            Run {
+                label: format!("{} < {}", exercise, fixture.stdin.display()),
+                expected,
+                output,
+            }
+        })
+        .collect()
+}
+
+/// Runs a compiled `run` or `qemu` exercise and checks every result,
+/// printing what differs. With `echo`, the binary's stdout is printed as well.
+pub fn run_and_check(exercise: &Exercise, compiled: &CompiledExercise, echo: bool) -> bool {
+    check_runs(exercise, &runs(exercise, compiled), echo)
+}
+
+/// Checks every run of an exercise, printing what differs. With `echo`, the
+/// binary's stdout is printed as well.
+pub fn check_runs(exercise: &Exercise, runs: &[Run], echo: bool) -> bool {
+    let fixtures = !exercise.fixtures.is_empty();
+
+    let mut passed = 0;
+    for run in runs {
+        if echo {
+            print!("{}", run.output.stdout);
+        }
+        if output_matches(&run.label, &run.expected, &run.output) {
+            if fixtures {
+                success!("{} passed", run.label);
+            }
+            passed += 1;
+        }
+    }
+
+    if fixtures {
+        progress!("{} of {} fixtures passed.", passed, runs.len());
+    }
+    passed == runs.len()
+}
+
+fn exit_mismatch(expected: &Expected, output: &ExerciseOutput) -> Option<String> {
+    if output.status == Some(expected.exit_code) {
+        return None;
+    }
+    let actual = match output.status {
+        Some(code) => code.to_string(),
+        None => "no exit code".to_string(),
+    };
+    Some(format!(
+        "exited with {}, but should exit with {}",
+        actual, expected.exit_code
+    ))
+}
+
+fn normalize(text: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::process;
+
+    /// Echoes stdin with its lines numbered, and exits with 3 if it was empty.
+    const SOURCE: &str = "use std::io::Read;\n\nfn main() {\n    let mut input = String::new();\n    std::io::stdin().read_to_string(&mut input).unwrap();\n    for (i, line) in input.lines().enumerate() {\n        println!(\"{}: {}\", i + 1, line);\n    }\n    if input.is_empty() {\n        std::process::exit(3);\n    }\n}\n";
+
+    fn output(stdout: &str, status: Option<i32>) -> ExerciseOutput {
+        ExerciseOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            status,
+            violation: None,
+        }
+    }
+
+    #[test]
+    fn ignores_trailing_whitespace_and_blank_lines() {
+        assert!(stdout_matches("a\nb\n", "a  \nb\n\n\n"));
+        assert!(stdout_matches("a\r\nb", "a\nb\n"));
+        assert!(!stdout_matches("a\nb\n", "a\n\nb\n"));
+        assert!(!stdout_matches("a\nb\n", " a\nb\n"));
+    }
+
+    #[test]
+    fn describes_each_difference() {
+        let expected = Expected {
+            stdout: Some("hi\n"),
+            exit_code: 0,
+        };
+        assert!(differences(&expected, &output("hi\n", Some(0))).is_empty());
+        assert_eq!(
+            differences(&expected, &output("ho\n", Some(1))),
+            [
+                "exited with 1, but should exit with 0",
+                "printed unexpected output"
+            ]
+        );
+        assert_eq!(
+            differences(&expected, &output("hi\n", None)),
+            ["exited with no exit code, but should exit with 0"]
+        );
+        let any_output = Expected {
+            stdout: None,
+            exit_code: 2,
+        };
+        assert!(differences(&any_output, &output("anything", Some(2))).is_empty());
+    }
+
+    #[test]
+    fn runs_once_per_fixture_with_its_stdin() {
+        let dir = env::temp_dir().join(format!("rustlings_{}_expect", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("numbered.rs"), SOURCE).unwrap();
+        fs::write(dir.join("two.txt"), "a\nb\n").unwrap();
+        fs::write(dir.join("empty.txt"), "").unwrap();
+        let exercise: Exercise = toml::from_str(&format!(
+            "name = \"expect_{0}\"\npath = \"{1}/numbered.rs\"\nmode = \"run\"\n\n\
+             [[fixtures]]\nstdin = \"{1}/two.txt\"\nexpected_output = \"1: a\\n2: b\\n\"\n\n\
+             [[fixtures]]\nstdin = \"{1}/empty.txt\"\nexit_code = 3\n\n\
+             [[fixtures]]\nstdin = \"{1}/two.txt\"\nexpected_output = \"1: b\\n\"\n\n\
+             [[fixtures]]\nstdin = \"{1}/missing.txt\"\n",
+            process::id(),
+            dir.display()
+        ))
+        .unwrap();
+
+        let compiled = exercise
+            .compile()
+            .unwrap_or_else(|output| panic!("{}", output.stderr));
+        let runs = runs(&exercise, &compiled);
+        let _ = fs::remove_dir_all(&dir);
+        let differences: Vec<Vec<String>> = runs
+            .iter()
+            .map(|run| differences(&run.expected, &run.output))
+            .collect();
+        assert_eq!(
+            differences,
+            [
+                vec![],
+                vec![],
+                vec!["printed unexpected output".to_string()],
+                vec!["exited with no exit code, but should exit with 0".to_string()],
+            ]
+        );
+        assert!(runs[0].label.ends_with("two.txt"));
+        assert!(!check_runs(&exercise, &runs, false));
+        assert!(check_runs(&exercise, &runs[..2], false));
+    }
+}
@@ -0,0 +1,83 @@
+use console::style;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::exercise::Explanation;
+
+/// Makes `rustc` and `clippy-driver` print diagnostics as JSON lines that
+/// still carry their human-readable rendering. `--json=diagnostic-rendered-ansi`
+/// would keep the colors, but the pinned nightly rejects it, so `render` adds
+/// them back to the headline itself.
+pub const RUSTC_JSON_ARGS: &[&str] = &["--error-format=json"];
+
+/// The part of a JSON diagnostic the runner looks at.
+#[derive(Deserialize)]
+struct Diagnostic {
+    message: String,
+    level: String,
+    code: Option<Code>,
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Code {
+    code: String,
+}
+
+impl Explanation {
+    fn matches(&self, diagnostic: &Diagnostic) -> bool {
+        let code = diagnostic.code.as_ref().map(|code| code.code.as_str());
+        if code != Some(self.code.as_str()) {
+            return false;
+        }
+        match &self.message {
+            None => true,
+            Some(message) => Regex::new(message).is_ok_and(|re| re.is_match(&diagnostic.message)),
+        }
+    }
+}
+
+/// Turns the JSON diagnostics on `stderr` back into the compiler's usual
+/// output, showing each explanation after the first diagnostic it matches.
+/// Lines that are not diagnostics are kept as they are.
+pub fn render(stderr: &str, explanations: &[Explanation]) -> String {
+    let mut shown = vec![false; explanations.len()];
+    let mut out = String::new();
+    for line in stderr.lines() {
+        let diagnostic: Diagnostic = match serde_json::from_str(line) {
+            Ok(diagnostic) => diagnostic,
+            Err(_) => {
+                out.push_str(line);
+                out.push('\n');
+                continue;
+            }
+        };
+        if let Some(rendered) = &diagnostic.rendered {
+            out.push_str(&colorize(&diagnostic.level, rendered));
+        }
+        for (explanation, shown) in explanations.iter().zip(shown.iter_mut()) {
+            if !*shown && explanation.matches(&diagnostic) {
+                *shown = true;
+                out.push_str(&format!(
+                    "{}\n{}\n\n",
+                    style("Explanation for this exercise:").bold().cyan(),
+                    explanation.text.trim_end()
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// Colors the `error`/`warning` headline of a plainly rendered diagnostic the
+/// way the compiler would in a terminal.
+pub fn colorize(level: &str, rendered: &str) -> String {
+    let (headline, rest) = rendered.split_at(rendered.find('\n').unwrap_or(rendered.len()));
+    let (label, message) = headline.split_at(headline.find(':').unwrap_or(0));
+    let label = match level {
+        "error" | "error: internal compiler error" => style(label).red().bold(),
+        "warning" => style(label).yellow().bold(),
+        _ => style(label).bold(),
+    };
+    format!("{}{}{}", label, style(message).bold(), rest)
+}
@@ -0,0 +1,159 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::exercise::{Exercise, Mode};
+use crate::report;
+use crate::sandbox;
+
+/// The module the hidden tests are spliced into. Its name doubles as the
+/// filter that keeps the visible tests from running a second time.
+const MODULE: &str = "rustlings_hidden_tests";
+
+/// Names the directory the `hidden_tests` of `info.toml` are relative to.
+/// Instructors keep it outside the student repository.
+pub const DIR_ENV: &str = "RUSTLINGS_HIDDEN_TESTS";
+
+/// The results of an exercise's hidden tests, kept apart from the visible ones.
+#[derive(Serialize, Debug)]
+pub struct HiddenTests {
+    pub passed: bool,
+    pub failing_tests: Vec<String>,
+    /// Compiler output, without colors, if the exercise and its hidden tests
+    /// failed to compile together.
+    pub diagnostics: Option<String>,
+}
+
+/// Returns the directory of hidden tests given by `flag`, or else by
+/// `DIR_ENV`, if it exists. Without one, grading skips hidden tests.
+pub fn dir(flag: Option<&str>) -> Option<PathBuf> {
+    let dir = match flag {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os(DIR_ENV)?),
+    };
+    Some(dir).filter(|dir| dir.is_dir())
+}
+
+/// Splices the `hidden_tests` of an exercise from `dir` into a copy of it as
+/// a `#[cfg(test)]` module that can `use super::*`, then compiles and runs
+/// only those tests, under the exercise's limits in case a solution
+/// special-cases the visible inputs with something that loops on others.
+/// Returns `None` if the exercise has no hidden tests in `dir`.
+pub fn run(exercise: &Exercise, dir: &Path) -> Option<HiddenTests> {
+    let module = dir.join(exercise.hidden_tests.as_ref()?);
+    if !module.is_file() {
+        return None;
+    }
+    let failed = |diagnostics: String| HiddenTests {
+        passed: false,
+        failing_tests: vec![],
+        diagnostics: Some(diagnostics),
+    };
+    if exercise.is_crate() {
+        let message = "hidden tests are not supported for crate exercises";
+        return Some(failed(message.to_string()));
+    }
+
+    let source = match (
+        fs::read_to_string(&exercise.path),
+        fs::read_to_string(&module),
+    ) {
+        (Ok(source), Ok(tests)) => splice(&source, &tests),
+        (Err(e), _) | (_, Err(e)) => return Some(failed(e.to_string())),
+    };
+
+    let mut spliced = exercise.clone();
+    spliced.name = format!("{}-hidden", exercise.name);
+    spliced.mode = Mode::Test;
+    spliced.path = spliced.temp_binary().with_extension("rs");
+    if let Err(e) = fs::write(&spliced.path, source) {
+        return Some(failed(e.to_string()));
+    }
+    let compiled = spliced.compile();
+    let _ = fs::remove_file(&spliced.path);
+    let compiled = match compiled {
+        Ok(compiled) => compiled,
+        Err(output) => return Some(failed(report::strip(&output.stderr))),
+    };
+
+    let mut cmd = compiled.command();
+    cmd.arg(format!("{}::", MODULE));
+    Some(match sandbox::output(&cmd, &exercise.limits, None) {
+        Ok((output, None)) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let prefix = format!("{}::", MODULE);
+            HiddenTests {
+                passed: output.status.success(),
+                failing_tests: report::failing_tests(&stdout)
+                    .into_iter()
+                    .map(|test| test.trim_start_matches(&prefix).to_string())
+                    .collect(),
+                diagnostics: None,
+            }
+        }
+        Ok((_, Some(violation))) => failed(format!("the hidden tests {}", violation)),
+        Err(e) => failed(e.to_string()),
+    })
+}
+
+/// Appends `tests` to `source` in a module of their own.
+fn splice(source: &str, tests: &str) -> String {
+    format!(
+        "{}\n\n#[cfg(test)]\nmod {} {{\n#[allow(unused_imports)]\nuse super::*;\n\n{}\n}}\n",
+        source, MODULE, tests
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::process;
+
+    const SOURCE: &str = "fn double(x: u32) -> u32 {\n    if x == 2 { 4 } else { x }\n}\n\n#[test]\nfn visible() {\n    assert!(false);\n}\n";
+
+    fn exercise(name: &str, hidden_tests: &str) -> Exercise {
+        toml::from_str(&format!(
+            "name = \"{}\"\npath = \"{}.rs\"\nmode = \"test\"\nhidden_tests = \"{}\"",
+            name, name, hidden_tests
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn splices_tests_into_a_module_of_their_own() {
+        let spliced = splice("fn main() {}\n", "#[test]\nfn t() {}");
+        assert!(spliced.starts_with("fn main() {}\n"));
+        assert!(spliced.contains("\n#[cfg(test)]\nmod rustlings_hidden_tests {\n"));
+        assert!(spliced.contains("use super::*;\n\n#[test]\nfn t() {}\n}\n"));
+    }
+
+    #[test]
+    fn runs_only_the_hidden_tests() {
+        let dir = env::temp_dir().join(format!("rustlings_{}_hidden", process::id()));
+        let hidden_dir = dir.join("hidden");
+        fs::create_dir_all(&hidden_dir).unwrap();
+        let mut exercise = exercise(&format!("hidden_{}", process::id()), "double.rs");
+        exercise.path = dir.join("double.rs");
+        fs::write(&exercise.path, SOURCE).unwrap();
+        fs::write(
+            hidden_dir.join("double.rs"),
+            "#[test]\nfn two() {\n    assert_eq!(double(2), 4);\n}\n\n#[test]\nfn three() {\n    assert_eq!(double(3), 6);\n}\n",
+        )
+        .unwrap();
+
+        let hidden = run(&exercise, &hidden_dir).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(!hidden.passed);
+        assert_eq!(hidden.failing_tests, vec!["three"]);
+        assert_eq!(hidden.diagnostics, None);
+    }
+
+    #[test]
+    fn skips_exercises_without_hidden_tests_in_the_dir() {
+        let exercise = exercise("a", "missing.rs");
+        assert!(run(&exercise, &env::temp_dir()).is_none());
+    }
+}
@@ -0,0 +1,45 @@
+use console::style;
+
+use crate::exercise::Exercise;
+use crate::progress::{self, Progress};
+
+/// Prints the hints of an exercise revealed so far plus the next one, and
+/// records the reveal.
+pub fn reveal(exercise: &Exercise) {
+    let hints = exercise.hints();
+    if hints.is_empty() {
+        println!("There are no hints for {}.", exercise.name);
+        return;
+    }
+
+    let shown = Progress::load()
+        .ok()
+        .and_then(|progress| progress.get(&exercise.name).map(|r| r.hints))
+        .unwrap_or(0) as usize;
+    let level = shown.min(hints.len() - 1);
+
+    if hints.len() == 1 {
+        println!("{}", hints[0]);
+    } else {
+        for (i, hint) in hints.iter().enumerate().take(level + 1) {
+            let heading = format!("Hint {} of {}:", i + 1, hints.len());
+            if i == level {
+                println!("{}", style(heading).bold());
+                println!("{}", hint);
+            } else {
+                println!("{}", style(heading).dim());
+                println!("{}", style(hint).dim());
+            }
+            println!();
+        }
+        if level + 1 < hints.len() {
+            println!(
+                "Run `rustlings hint {}` again for the next hint.",
+                exercise.name
+            );
+        } else {
+            println!("That was the last hint.");
+        }
+    }
+    progress::record_hint(&exercise.name);
+}
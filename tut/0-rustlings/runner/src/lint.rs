@@ -0,0 +1,596 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use toml::Value;
+
+use crate::exercise::{self, Exercise, ExerciseList, Mode};
+use crate::hidden;
+use crate::package;
+
+const TEST_ATTRIBUTE_REGEX: &str = r"(?m)^\s*#\[test\]";
+const MAIN_REGEX: &str = r"(?m)^\s*fn main\(";
+const COMMAND_REGEX: &str = r"rustlings (?:hint|run) ([\w./-]*\w)";
+const EXERCISES_DIR: &str = "exercises";
+
+/// Fields that only mean something for some modes.
+const MODE_FIELDS: &[(&str, &[Mode])] = &[
+    ("expected_output", &[Mode::Run, Mode::Qemu]),
+    ("exit_code", &[Mode::Run, Mode::Qemu]),
+    ("fixtures", &[Mode::Run]),
+    ("lint_level", &[Mode::Clippy]),
+    ("lints", &[Mode::Clippy]),
+    ("target", &[Mode::Xbuild, Mode::Qemu]),
+    ("limits", &[Mode::Test, Mode::Run, Mode::Qemu]),
+    (
+        "hidden_tests",
+        &[Mode::Compile, Mode::Test, Mode::Run, Mode::Clippy],
+    ),
+    (
+        "explanations",
+        &[Mode::Compile, Mode::Test, Mode::Run, Mode::Clippy],
+    ),
+];
+
+/// Something wrong with `info.toml` or the files it refers to.
+pub struct Problem {
+    /// The entry at fault, e.g. `variables1`, or the file if no entry is.
+    pub entry: String,
+    pub message: String,
+}
+
+/// Checks every entry of the exercise list at `path` against the schema and
+/// against the file it refers to, and looks for exercise files no entry
+/// refers to.
+pub fn lint(path: &Path) -> io::Result<Vec<Problem>> {
+    let text = fs::read_to_string(path)?;
+    let (mut problems, paths) = check_list(&text, &path.display().to_string());
+
+    let mut files = vec![];
+    package::rust_files(Path::new(EXERCISES_DIR), &mut files)?;
+    for file in files {
+        if !paths.iter().any(|path| file.starts_with(path)) {
+            problems.push(Problem {
+                entry: file.display().to_string(),
+                message: "not referred to by any entry in info.toml".to_string(),
+            });
+        }
+    }
+    Ok(problems)
+}
+
+/// Checks the exercise list `text`, read from `file`, and the files its
+/// entries refer to. Returns the problems and the paths of the entries.
+fn check_list(text: &str, file: &str) -> (Vec<Problem>, Vec<PathBuf>) {
+    let problem = |entry: &str, message: String| Problem {
+        entry: entry.to_string(),
+        message,
+    };
+
+    let root: Value = match toml::from_str(text) {
+        Ok(root) => root,
+        Err(e) => return (vec![problem(file, e.to_string())], vec![]),
+    };
+    let mut problems = vec![];
+    let root = root.as_table().expect("a TOML document is a table");
+    for key in root.keys().filter(|key| *key != "exercises") {
+        problems.push(problem(file, format!("unknown top-level key `{}`", key)));
+    }
+    let entries = match root.get("exercises").and_then(Value::as_array) {
+        Some(entries) => entries,
+        None => {
+            problems.push(problem(file, "no `[[exercises]]` entries".to_string()));
+            return (problems, vec![]);
+        }
+    };
+
+    let mut parsed = vec![];
+    let mut names: HashMap<String, usize> = HashMap::new();
+    let mut paths: HashMap<PathBuf, usize> = HashMap::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let label = entry
+            .get("name")
+            .and_then(Value::as_str)
+            .map_or_else(|| format!("entry #{}", i + 1), str::to_string);
+
+        if let Some(name) = entry.get("name").and_then(Value::as_str) {
+            if let Some(first) = names.insert(name.to_string(), i) {
+                let message = format!("duplicate name, first used by entry #{}", first + 1);
+                problems.push(problem(&label, message));
+            }
+        }
+        if let Some(path) = entry.get("path").and_then(Value::as_str) {
+            if let Some(first) = paths.insert(PathBuf::from(path), i) {
+                let message = format!("duplicate path, first used by entry #{}", first + 1);
+                problems.push(problem(&label, message));
+            }
+        }
+
+        let exercise: Exercise = match entry.clone().try_into() {
+            Ok(exercise) => exercise,
+            Err(e) => {
+                problems.push(problem(&label, e.to_string()));
+                continue;
+            }
+        };
+        let table = entry.as_table().expect("deserialized from a table");
+        for (field, modes) in MODE_FIELDS {
+            if table.contains_key(*field) && !modes.contains(&exercise.mode) {
+                let message = format!("`{}` is ignored by `{}` exercises", field, exercise.mode);
+                problems.push(problem(&label, message));
+            }
+        }
+
+        for message in check_exercise(&exercise) {
+            problems.push(problem(&label, message));
+        }
+        parsed.push(exercise);
+    }
+
+    // Report unknown and self-requirements per entry; any other failure is a
+    // cycle between entries.
+    let list = ExerciseList { exercises: parsed };
+    let before = problems.len();
+    for exercise in &list.exercises {
+        for name in exercise.requires.iter().flatten() {
+            if *name == exercise.name {
+                problems.push(problem(&exercise.name, "requires itself".to_string()));
+            } else if list.find(name).is_none() {
+                let message = format!("requires unknown exercise `{}`", name);
+                problems.push(problem(&exercise.name, message));
+            }
+        }
+    }
+    if let Err(message) = list.check_requirements() {
+        if problems.len() == before {
+            problems.push(problem(file, message));
+        }
+    }
+
+    (problems, paths.into_keys().collect())
+}
+
+/// Checks a well-formed entry against the file it refers to.
+fn check_exercise(exercise: &Exercise) -> Vec<String> {
+    let mut problems = vec![];
+
+    let stem = exercise.path.file_stem().and_then(|s| s.to_str());
+    if exercise.is_crate() {
+        problems.extend(check_crate(exercise));
+    } else if exercise.path.extension().is_none_or(|ext| ext != "rs") {
+        problems.push(format!("`{}` is not a `.rs` file", exercise.path.display()));
+    } else if stem != Some(exercise.name.as_str()) {
+        problems.push(format!(
+            "name does not match its file `{}`",
+            exercise.path.display()
+        ));
+    }
+    if !exercise.path.starts_with(EXERCISES_DIR) {
+        problems.push(format!(
+            "`{}` is outside `exercises/`",
+            exercise.path.display()
+        ));
+    }
+    for fixture in &exercise.fixtures {
+        if !fixture.stdin.is_file() {
+            problems.push(format!(
+                "fixture `{}` does not exist",
+                fixture.stdin.display()
+            ));
+        }
+    }
+
+    if let Some(hidden) = &exercise.hidden_tests {
+        if hidden.is_absolute() {
+            problems.push(format!(
+                "hidden tests `{}` must be relative to ${}",
+                hidden.display(),
+                hidden::DIR_ENV
+            ));
+        } else if let Some(dir) = hidden::dir(None) {
+            if !dir.join(hidden).is_file() {
+                problems.push(format!(
+                    "hidden tests `{}` do not exist in {}",
+                    hidden.display(),
+                    dir.display()
+                ));
+            }
+        }
+    }
+
+    for required in &exercise.required {
+        if let Err(e) = Regex::new(&required.pattern) {
+            problems.push(format!(
+                "required line `{}` has an invalid `pattern`: {}",
+                required.reason, e
+            ));
+        }
+    }
+
+    for explanation in &exercise.explanations {
+        if let Some(Err(e)) = explanation.message.as_deref().map(Regex::new) {
+            problems.push(format!(
+                "explanation for `{}` has an invalid `message`: {}",
+                explanation.code, e
+            ));
+        }
+    }
+
+    if exercise.hints().is_empty() {
+        problems.push("has no `hint` or `hints`".to_string());
+    } else if !exercise.hint.is_empty() && !exercise.hints.is_empty() {
+        problems.push("`hint` is ignored because `hints` is given".to_string());
+    }
+    let commands = Regex::new(COMMAND_REGEX).unwrap();
+    for hint in exercise.hints() {
+        for caps in commands.captures_iter(hint) {
+            if !refers_to(exercise, &caps[1]) {
+                problems.push(format!("hint refers to `{}`", &caps[0]));
+            }
+        }
+    }
+
+    match read_sources(exercise) {
+        Ok(source) => problems.extend(check_source(exercise, &source)),
+        Err(e) => problems.push(format!("cannot read `{}`: {}", exercise.path.display(), e)),
+    }
+    problems
+}
+
+/// Checks the source of an exercise, all of its files in one string.
+fn check_source(exercise: &Exercise, source: &str) -> Vec<String> {
+    let mut problems = vec![];
+    if !source.lines().any(exercise::is_done_marker) {
+        problems.push("has no `// I AM NOT DONE` marker".to_string());
+    }
+    let commands = Regex::new(COMMAND_REGEX).unwrap();
+    for caps in commands.captures_iter(source) {
+        if !refers_to(exercise, &caps[1]) {
+            problems.push(format!("file refers to `{}`", &caps[0]));
+        }
+    }
+
+    // `test` exercises without tests are fine as long as they have no `main`:
+    // they are how libraries are compiled.
+    let has_tests = Regex::new(TEST_ATTRIBUTE_REGEX).unwrap().is_match(source);
+    let has_main = Regex::new(MAIN_REGEX).unwrap().is_match(source);
+    match exercise.mode {
+        Mode::Test if !has_tests && has_main => problems.push(
+            "is a `test` exercise, but has no `#[test]` functions; make it `compile`".to_string(),
+        ),
+        Mode::Compile | Mode::Run | Mode::Clippy if has_tests => problems.push(format!(
+            "has `#[test]` functions that a `{}` exercise never runs",
+            exercise.mode
+        )),
+        _ => {}
+    }
+
+    problems
+}
+
+/// Checks what only applies to crate exercises: the package itself, the modes
+/// and fields they support, and that they build offline.
+fn check_crate(exercise: &Exercise) -> Vec<String> {
+    let mut problems = vec![];
+    if exercise.path.file_name().and_then(|s| s.to_str()) != Some(exercise.name.as_str()) {
+        problems.push(format!(
+            "name does not match its directory `{}`",
+            exercise.path.display()
+        ));
+    }
+    if !matches!(exercise.mode, Mode::Compile | Mode::Test | Mode::Run) {
+        problems.push(format!(
+            "crate exercises cannot be `{}` exercises",
+            exercise.mode
+        ));
+    }
+    if exercise.hidden_tests.is_some() {
+        problems.push("`hidden_tests` are not supported for crate exercises".to_string());
+    }
+    if !exercise.explanations.is_empty() {
+        problems.push("`explanations` are not supported for crate exercises".to_string());
+    }
+
+    let mut manifests = vec![];
+    if let Err(e) = manifest_files(&exercise.path, &mut manifests) {
+        problems.push(format!("cannot read `{}`: {}", exercise.path.display(), e));
+    }
+    if !manifests.contains(&exercise.path.join(package::MANIFEST)) {
+        problems.push(format!(
+            "`{}` has no `{}`",
+            exercise.path.display(),
+            package::MANIFEST
+        ));
+    }
+    for manifest in manifests {
+        match registry_dependencies(&manifest) {
+            Ok(names) => {
+                for name in names {
+                    problems.push(format!(
+                        "`{}` depends on `{}` from a registry, which cannot be built offline",
+                        manifest.display(),
+                        name
+                    ));
+                }
+            }
+            Err(e) => problems.push(format!("cannot read `{}`: {}", manifest.display(), e)),
+        }
+    }
+    problems
+}
+
+/// Collects the manifests under `dir`, recursively, leaving out build output.
+fn manifest_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if !path.ends_with("target") {
+                manifest_files(&path, files)?;
+            }
+        } else if path.ends_with(package::MANIFEST) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the dependencies of a manifest that are not given by `path`.
+fn registry_dependencies(manifest: &Path) -> io::Result<Vec<String>> {
+    let text = fs::read_to_string(manifest)?;
+    let root: Value =
+        toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut names = vec![];
+    for section in &["dependencies", "dev-dependencies", "build-dependencies"] {
+        let dependencies = match root.get(section).and_then(Value::as_table) {
+            Some(dependencies) => dependencies,
+            None => continue,
+        };
+        for (name, dependency) in dependencies {
+            if dependency.get("path").is_none() {
+                names.push(name.clone());
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Reads every Rust file of the exercise into one string.
+fn read_sources(exercise: &Exercise) -> io::Result<String> {
+    let mut source = String::new();
+    for file in exercise.source_files()? {
+        source.push_str(&fs::read_to_string(file)?);
+        source.push('\n');
+    }
+    Ok(source)
+}
+
+/// Returns whether a `rustlings hint` or `rustlings run` argument names the
+/// exercise, by name or by path.
+fn refers_to(exercise: &Exercise, arg: &str) -> bool {
+    arg == exercise.name || Path::new(arg) == exercise.path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    /// The problems `check_list` finds in `text`, as `entry: message`.
+    fn problems(text: &str) -> Vec<String> {
+        check_list(text, "info.toml")
+            .0
+            .into_iter()
+            .map(|p| format!("{}: {}", p.entry, p.message))
+            .collect()
+    }
+
+    fn has(problems: &[String], expected: &str) -> bool {
+        problems.iter().any(|p| p == expected)
+    }
+
+    fn exercise(mode: &str) -> Exercise {
+        let entry = format!(
+            "name = \"a\"\npath = \"exercises/a.rs\"\nmode = \"{}\"\nhint = \"Look closer.\"",
+            mode
+        );
+        toml::from_str(&entry).unwrap()
+    }
+
+    #[test]
+    fn reports_malformed_lists() {
+        let found = problems("[[exercises]\n");
+        assert_eq!(found.len(), 1);
+        assert!(found[0].starts_with("info.toml: "));
+
+        assert_eq!(
+            problems("[other]\n"),
+            vec![
+                "info.toml: unknown top-level key `other`",
+                "info.toml: no `[[exercises]]` entries",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_schema_errors_and_duplicates() {
+        let found = problems(
+            "[[exercises]]\nname = \"a\"\npath = \"exercises/a.rs\"\nmode = \"test\"\nhint = \"h\"\ncolour = \"red\"\n\
+             [[exercises]]\nname = \"a\"\npath = \"exercises/a.rs\"\nmode = \"test\"\nhint = \"h\"\n\
+             [[exercises]]\npath = \"exercises/b.rs\"\nmode = \"sing\"\n",
+        );
+        assert!(found
+            .iter()
+            .any(|p| p.starts_with("a: ") && p.contains("colour")));
+        assert!(has(&found, "a: duplicate name, first used by entry #1"));
+        assert!(has(&found, "a: duplicate path, first used by entry #1"));
+        assert!(found.iter().any(|p| p.starts_with("entry #3: ")));
+    }
+
+    #[test]
+    fn reports_fields_the_mode_ignores() {
+        let found = problems(
+            "[[exercises]]\nname = \"a\"\npath = \"exercises/a.rs\"\nmode = \"test\"\nhint = \"h\"\nexpected_output = \"hi\"\ntarget = \"x\"\n",
+        );
+        assert!(has(
+            &found,
+            "a: `expected_output` is ignored by `test` exercises"
+        ));
+        assert!(has(&found, "a: `target` is ignored by `test` exercises"));
+    }
+
+    #[test]
+    fn reports_bad_requirements() {
+        let entry = |name: &str, requires: &str| {
+            format!(
+                "[[exercises]]\nname = \"{0}\"\npath = \"exercises/{0}.rs\"\nmode = \"test\"\nhint = \"h\"\nrequires = {1}\n",
+                name, requires
+            )
+        };
+        let found = problems(&(entry("a", "[\"a\"]") + &entry("b", "[\"z\"]")));
+        assert!(has(&found, "a: requires itself"));
+        assert!(has(&found, "b: requires unknown exercise `z`"));
+
+        let found = problems(&(entry("a", "[\"b\"]") + &entry("b", "[\"a\"]")));
+        assert!(found.iter().any(|p| p.starts_with("info.toml: ")));
+    }
+
+    #[test]
+    fn reports_paths_that_do_not_fit_the_entry() {
+        let mut wrong = exercise("test");
+        wrong.path = PathBuf::from("exercises/b.rs");
+        assert!(check_exercise(&wrong)
+            .contains(&"name does not match its file `exercises/b.rs`".to_string()));
+
+        wrong.path = PathBuf::from("exercises/a.txt");
+        assert!(
+            check_exercise(&wrong).contains(&"`exercises/a.txt` is not a `.rs` file".to_string())
+        );
+
+        wrong.path = PathBuf::from("src/a.rs");
+        assert!(check_exercise(&wrong).contains(&"`src/a.rs` is outside `exercises/`".to_string()));
+    }
+
+    #[test]
+    fn reports_missing_and_misplaced_files() {
+        let mut bad = exercise("run");
+        bad.fixtures = toml::from_str::<Exercise>(
+            "name = \"a\"\npath = \"exercises/a.rs\"\nmode = \"run\"\n[[fixtures]]\nstdin = \"fixtures/none.txt\"\n",
+        )
+        .unwrap()
+        .fixtures;
+        bad.hidden_tests = Some(PathBuf::from("/hidden/a.rs"));
+        let found = check_exercise(&bad);
+        assert!(found.contains(&"fixture `fixtures/none.txt` does not exist".to_string()));
+        assert!(found.contains(&format!(
+            "hidden tests `/hidden/a.rs` must be relative to ${}",
+            hidden::DIR_ENV
+        )));
+        assert!(found
+            .iter()
+            .any(|p| p.starts_with("cannot read `exercises/a.rs`")));
+    }
+
+    #[test]
+    fn reports_invalid_patterns() {
+        let bad: Exercise = toml::from_str(
+            "name = \"a\"\npath = \"exercises/a.rs\"\nmode = \"compile\"\nhint = \"h\"\n\
+             required = [{ pattern = \"(\", reason = \"Keep it.\" }]\n\
+             [[explanations]]\ncode = \"E0308\"\nmessage = \"[\"\ntext = \"Types differ.\"\n",
+        )
+        .unwrap();
+        let found = check_exercise(&bad);
+        assert!(found
+            .iter()
+            .any(|p| p.starts_with("required line `Keep it.` has an invalid `pattern`")));
+        assert!(found
+            .iter()
+            .any(|p| p.starts_with("explanation for `E0308` has an invalid `message`")));
+    }
+
+    #[test]
+    fn reports_missing_ambiguous_and_stale_hints() {
+        let mut bad = exercise("test");
+        bad.hint = String::new();
+        assert!(check_exercise(&bad).contains(&"has no `hint` or `hints`".to_string()));
+
+        bad.hint = "Look closer.".to_string();
+        bad.hints = vec!["Try `rustlings hint b`.".to_string()];
+        let found = check_exercise(&bad);
+        assert!(found.contains(&"`hint` is ignored because `hints` is given".to_string()));
+        assert!(found.contains(&"hint refers to `rustlings hint b`".to_string()));
+
+        bad.hints = vec!["Run `rustlings run exercises/a.rs` or `rustlings hint a`.".to_string()];
+        assert!(!check_exercise(&bad).iter().any(|p| p.starts_with("hint ")));
+    }
+
+    #[test]
+    fn reports_sources_without_a_marker_or_with_stale_commands() {
+        let source = "// Execute `rustlings hint b` for hints!\nfn main() {}\n";
+        assert_eq!(
+            check_source(&exercise("compile"), source),
+            vec![
+                "has no `// I AM NOT DONE` marker",
+                "file refers to `rustlings hint b`",
+            ]
+        );
+        let source = "// Execute `rustlings hint a` for hints!\n// I AM NOT DONE\nfn main() {}\n";
+        assert!(check_source(&exercise("compile"), source).is_empty());
+    }
+
+    #[test]
+    fn reports_tests_that_the_mode_does_not_run() {
+        let main = "// I AM NOT DONE\nfn main() {}\n";
+        let library = "// I AM NOT DONE\npub fn f() {}\n";
+        let tests = "// I AM NOT DONE\n#[test]\nfn t() {}\n";
+
+        assert_eq!(
+            check_source(&exercise("test"), main),
+            vec!["is a `test` exercise, but has no `#[test]` functions; make it `compile`"]
+        );
+        assert!(check_source(&exercise("test"), library).is_empty());
+        assert!(check_source(&exercise("test"), tests).is_empty());
+        for mode in &["compile", "run", "clippy"] {
+            assert_eq!(
+                check_source(&exercise(mode), tests),
+                vec![format!(
+                    "has `#[test]` functions that a `{}` exercise never runs",
+                    mode
+                )]
+            );
+        }
+    }
+
+    #[test]
+    fn reports_crates_that_cannot_be_built_offline() {
+        let dir = env::temp_dir().join(format!("rustlings_{}_lint", process::id()));
+        let package = dir.join("b");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(
+            package.join(package::MANIFEST),
+            "[package]\nname = \"b\"\nversion = \"0.1.0\"\n\n[dependencies]\nlocal = { path = \"../local\" }\nrand = \"0.8\"\n",
+        )
+        .unwrap();
+
+        let mut bad = exercise("clippy");
+        bad.path = package.clone();
+        bad.hidden_tests = Some(PathBuf::from("a.rs"));
+        let found = check_crate(&bad);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            found,
+            vec![
+                format!("name does not match its directory `{}`", package.display()),
+                "crate exercises cannot be `clippy` exercises".to_string(),
+                "`hidden_tests` are not supported for crate exercises".to_string(),
+                format!(
+                    "`{}` depends on `rand` from a registry, which cannot be built offline",
+                    package.join(package::MANIFEST).display()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,435 @@
+#[macro_use]
+mod ui;
+
+mod budget;
+mod cache;
+mod diff;
+mod doctor;
+mod exercise;
+mod expect;
+mod explain;
+mod hidden;
+mod hint;
+mod lint;
+mod mutate;
+mod package;
+mod pristine;
+mod progress;
+mod project;
+mod protect;
+mod qemu;
+mod report;
+mod reset;
+mod run;
+mod sandbox;
+mod scaffold;
+mod selftest;
+mod status;
+mod topics;
+mod tui;
+mod verify;
+mod watch;
+mod xbuild;
+
+use std::path::Path;
+use std::process;
+
+use clap::{crate_version, App, AppSettings, Arg, ArgMatches, SubCommand};
+use console::style;
+
+use crate::exercise::{Exercise, ExerciseList, Mode};
+
+const INFO_TOML: &str = "info.toml";
+
+fn main() {
+    let matches = App::new("rustlings")
+        .version(crate_version!())
+        .about("Small exercises to get you used to reading and writing Rust code")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("verify")
+                .about("Verifies every exercise whose requirements are done")
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .takes_value(true)
+                        .possible_values(report::FORMATS)
+                        .help("Check every exercise and print a report for graders instead"),
+                )
+                .arg(
+                    Arg::with_name("hidden-tests")
+                        .long("hidden-tests")
+                        .takes_value(true)
+                        .requires("format")
+                        .help(
+                            "Grade with the hidden tests in this directory; defaults to $RUSTLINGS_HIDDEN_TESTS",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("jobs")
+                        .short("j")
+                        .long("jobs")
+                        .takes_value(true)
+                        .help("Check this many exercises at once; defaults to the number of cores"),
+                )
+                .arg(
+                    Arg::with_name("no-cache")
+                        .long("no-cache")
+                        .help("Check exercises again even if they are unchanged since they passed"),
+                )
+                .arg(topic_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("watch")
+                .about("Reruns `verify` whenever an exercise is saved")
+                .arg(topic_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("run")
+                .about("Compiles and runs or tests a single exercise")
+                .arg(name_arg()),
+        )
+        .subcommand(SubCommand::with_name("tui").about(
+            "Browses exercises by section in a full-screen interface, checking them on save",
+        ))
+        .subcommand(
+            SubCommand::with_name("hint")
+                .about("Reveals the next hint for a single exercise")
+                .arg(name_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("reset")
+                .about("Restores exercises as handed out, backing up your versions first")
+                .arg(
+                    Arg::with_name("name")
+                        .index(1)
+                        .required_unless("all")
+                        .help("The exercise, or the section, e.g. `cs140e`, to restore"),
+                )
+                .arg(
+                    Arg::with_name("all")
+                        .long("all")
+                        .conflicts_with("name")
+                        .help("Restore every exercise"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("doctor")
+                .about("Checks the toolchain and tools bin/setup.sh installs, and how to fix them"),
+        )
+        .subcommand(
+            SubCommand::with_name("new")
+                .about("Scaffolds a new exercise and adds it to info.toml")
+                .arg(
+                    Arg::with_name("path")
+                        .index(1)
+                        .required(true)
+                        .help("The section and name of the exercise, e.g. `cs140e/borrow-3`"),
+                )
+                .arg(
+                    Arg::with_name("mode")
+                        .long("mode")
+                        .takes_value(true)
+                        .possible_values(scaffold::MODES)
+                        .default_value("compile")
+                        .help("How the exercise is checked"),
+                )
+                .arg(
+                    Arg::with_name("budget")
+                        .long("budget")
+                        .takes_value(true)
+                        .help("The diff budget of the exercise, e.g. `3 lines`"),
+                )
+                .arg(
+                    Arg::with_name("after")
+                        .long("after")
+                        .takes_value(true)
+                        .help("Add it after this exercise; defaults to the end of its section"),
+                )
+                .arg(
+                    Arg::with_name("hint")
+                        .long("hint")
+                        .takes_value(true)
+                        .help("The hint of the exercise"),
+                )
+                .arg(
+                    Arg::with_name("solution")
+                        .long("solution")
+                        .help("Also create a reference solution in solutions/"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("lint-info")
+                .about("Checks info.toml against its schema and the exercise files"),
+        )
+        .subcommand(
+            SubCommand::with_name("mutate")
+                .about("Reports mutants of reference solutions that the tests fail to catch")
+                .arg(
+                    Arg::with_name("name")
+                        .index(1)
+                        .help("Only mutate this exercise; defaults to every `test` exercise"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("selftest")
+                .about("Checks that every starter fails and every solution in solutions/ passes"),
+        )
+        .subcommand(
+            SubCommand::with_name("topics")
+                .about("Maps topics to their exercises and what each requires")
+                .arg(
+                    Arg::with_name("topic")
+                        .index(1)
+                        .help("List every exercise on the path to this topic, e.g. `lifetimes`"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("lsp").about(
+                "Writes rust-project.json so that rust-analyzer can check the exercise files",
+            ),
+        )
+        .subcommand(
+            SubCommand::with_name("status")
+                .about("Summarizes recorded progress per section")
+                .arg(
+                    Arg::with_name("section")
+                        .index(1)
+                        .help("Show every exercise of this section, e.g. `cs140e`"),
+                ),
+        )
+        .get_matches();
+
+    if !Path::new(INFO_TOML).exists() {
+        println!(
+            "rustlings must be run from the directory containing `{}` (tut/0-rustlings).",
+            INFO_TOML
+        );
+        process::exit(1);
+    }
+    if matches.subcommand_matches("lint-info").is_some() {
+        lint_info();
+        return;
+    }
+    if matches.subcommand_matches("doctor").is_some() {
+        if !doctor::doctor() {
+            process::exit(1);
+        }
+        return;
+    }
+    let list = ExerciseList::load(INFO_TOML).unwrap_or_else(|e| {
+        println!("Failed to read {}: {}", INFO_TOML, e);
+        println!("Run `rustlings lint-info` for details.");
+        process::exit(1);
+    });
+    if matches.subcommand_matches("lsp").is_none() {
+        project::refresh(&list, Path::new(INFO_TOML));
+    }
+
+    match matches.subcommand() {
+        ("verify", Some(args)) => {
+            let exercises = selected_exercises(&list, args);
+            let mut options = verify::Options::default();
+            if let Some(jobs) = args.value_of("jobs") {
+                options.jobs = jobs.parse().unwrap_or_else(|_| {
+                    println!("`--jobs` must be a number, not `{}`.", jobs);
+                    process::exit(1);
+                });
+            }
+            options.cache = !args.is_present("no-cache");
+            if let Some(format) = args.value_of("format") {
+                let hidden_dir = hidden::dir(args.value_of("hidden-tests"));
+                let reports = verify::evaluate(&exercises, options.jobs, hidden_dir.as_deref());
+                print!("{}", report::render(&reports, format));
+                if reports.iter().any(|r| r.outcome != report::Outcome::Passed) {
+                    process::exit(1);
+                }
+                return;
+            }
+            if verify::verify(&list, &exercises, &options).is_err() {
+                process::exit(1);
+            }
+            println!("🎉 All exercises completed! 🎉");
+        }
+        ("watch", Some(args)) => {
+            if let Err(e) = watch::watch(&list, &selected_exercises(&list, args)) {
+                println!("Error watching exercises: {}", e);
+                process::exit(1);
+            }
+        }
+        ("run", Some(args)) => {
+            let exercise = find_exercise(&list, args);
+            topics::warn_if_locked(&list, exercise);
+            let result = run::run(exercise);
+            progress::record_attempt(&exercise.name, false);
+            if result.is_err() {
+                process::exit(1);
+            }
+        }
+        ("tui", Some(_)) => {
+            if let Err(e) = tui::tui(&list) {
+                println!("Error running the interface: {}", e);
+                process::exit(1);
+            }
+        }
+        ("hint", Some(args)) => {
+            hint::reveal(find_exercise(&list, args));
+        }
+        ("reset", Some(args)) => {
+            let exercises: Vec<&Exercise> = match args.value_of("name") {
+                Some(name) => match list.find(name) {
+                    Some(exercise) => vec![exercise],
+                    None => list
+                        .sections()
+                        .into_iter()
+                        .find(|(section, _)| section == name)
+                        .map(|(_, exercises)| exercises)
+                        .unwrap_or_else(|| {
+                            println!("No exercise or section named `{}` in {}.", name, INFO_TOML);
+                            process::exit(1);
+                        }),
+                },
+                None => list.exercises.iter().collect(),
+            };
+            if !reset::reset(&exercises) {
+                process::exit(1);
+            }
+        }
+        ("new", Some(args)) => {
+            let path = args.value_of("path").unwrap();
+            let (section, name) = path.split_once('/').unwrap_or_else(|| {
+                println!(
+                    "`{}` must be `<section>/<name>`, e.g. `cs140e/borrow-3`.",
+                    path
+                );
+                process::exit(1);
+            });
+            let mode = toml::Value::String(args.value_of("mode").unwrap().to_string())
+                .try_into()
+                .unwrap();
+            let new = scaffold::NewExercise {
+                section,
+                name,
+                mode,
+                budget: args.value_of("budget"),
+                after: args.value_of("after"),
+                hint: args.value_of("hint"),
+                solution: args.is_present("solution"),
+            };
+            if let Err(e) = scaffold::scaffold(&list, &new, Path::new(INFO_TOML)) {
+                println!("Failed to create {}: {}", path, e);
+                process::exit(1);
+            }
+            lint_info();
+        }
+        ("mutate", Some(args)) => {
+            let exercises: Vec<&Exercise> = match args.value_of("name") {
+                Some(_) => vec![find_exercise(&list, args)],
+                None => list
+                    .exercises
+                    .iter()
+                    .filter(|e| e.mode == Mode::Test && !e.is_crate())
+                    .collect(),
+            };
+            if !mutate::mutate(&exercises) {
+                process::exit(1);
+            }
+        }
+        ("selftest", Some(_)) => {
+            if !selftest::selftest(&list) {
+                process::exit(1);
+            }
+        }
+        ("topics", Some(args)) => {
+            if let Err(e) = topics::topics(&list, args.value_of("topic")) {
+                println!("Failed to read {}: {}", progress::PROGRESS_FILE, e);
+                process::exit(1);
+            }
+        }
+        ("lsp", Some(_)) => {
+            if let Err(e) = project::generate(&list) {
+                println!("Failed to write {}: {}", project::PROJECT_FILE, e);
+                process::exit(1);
+            }
+            success!(
+                "Wrote {} for {} exercises.",
+                project::PROJECT_FILE,
+                list.exercises.len()
+            );
+            println!(
+                "If your editor opens a parent directory, point `rust-analyzer.linkedProjects` at it."
+            );
+        }
+        ("status", Some(args)) => {
+            if let Err(e) = status::status(&list, args.value_of("section")) {
+                println!("Failed to read {}: {}", progress::PROGRESS_FILE, e);
+                process::exit(1);
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn lint_info() {
+    let problems = lint::lint(Path::new(INFO_TOML)).unwrap_or_else(|e| {
+        println!("Failed to read {}: {}", INFO_TOML, e);
+        process::exit(1);
+    });
+    if problems.is_empty() {
+        success!("{} looks good!", INFO_TOML);
+        return;
+    }
+    for problem in &problems {
+        println!("{}: {}", style(&problem.entry).bold(), problem.message);
+    }
+    println!();
+    let plural = if problems.len() == 1 { "" } else { "s" };
+    warn!(
+        "Found {} problem{} in {}.",
+        problems.len(),
+        plural,
+        INFO_TOML
+    );
+    process::exit(1);
+}
+
+fn name_arg() -> Arg<'static, 'static> {
+    Arg::with_name("name")
+        .required(true)
+        .index(1)
+        .help("The name of the exercise, as given in info.toml")
+}
+
+fn topic_arg() -> Arg<'static, 'static> {
+    Arg::with_name("topic")
+        .long("topic")
+        .takes_value(true)
+        .help("Only go through this topic and the exercises it builds on")
+}
+
+/// Returns the exercises on the path to `--topic`, or all of them, each after
+/// the exercises it requires.
+fn selected_exercises<'a>(list: &'a ExerciseList, args: &ArgMatches) -> Vec<&'a Exercise> {
+    match args.value_of("topic") {
+        Some(topic) => {
+            let path = list.path(topic);
+            if path.is_empty() {
+                println!(
+                    "No topic named `{}`; run `rustlings topics` to list them.",
+                    topic
+                );
+                process::exit(1);
+            }
+            path
+        }
+        None => list.ordered(),
+    }
+}
+
+fn find_exercise<'a>(list: &'a ExerciseList, args: &ArgMatches) -> &'a Exercise {
+    let name = args.value_of("name").unwrap();
+    list.find(name).unwrap_or_else(|| {
+        println!("No exercise named `{}` in {}.", name, INFO_TOML);
+        process::exit(1);
+    })
+}
@@ -0,0 +1,562 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+use std::process;
+
+use crate::diff;
+use crate::exercise::{Exercise, Limits, Mode};
+use crate::protect;
+use crate::sandbox;
+use crate::selftest;
+
+/// Binary operators and what each is mutated into. Only operators with a
+/// space on either side are mutated, so that `<` and `>` in generics are not.
+const OPERATORS: &[(&str, &str)] = &[
+    (" == ", " != "),
+    (" != ", " == "),
+    (" < ", " >= "),
+    (" > ", " <= "),
+    (" <= ", " > "),
+    (" >= ", " < "),
+    (" && ", " || "),
+    (" || ", " && "),
+];
+
+/// How many seconds the tests of a single mutant may run before it counts as
+/// killed.
+const MUTANT_TIMEOUT: u64 = 5;
+
+/// A reference solution with a single small change.
+pub struct Mutant {
+    /// The 1-based line of the change.
+    pub line: usize,
+    pub description: String,
+    pub source: String,
+}
+
+/// What the exercise's tests made of a mutant.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Fate {
+    /// A test failed or timed out.
+    Killed,
+    /// Every test passed.
+    Survived,
+    /// The mutant did not compile.
+    Unviable,
+}
+
+/// Mutates the reference solution of each `test` exercise and reports the
+/// mutants its tests let through. Returns whether every mutant was killed.
+pub fn mutate(exercises: &[&Exercise]) -> bool {
+    let scratch = env::temp_dir().join(format!("rustlings_{}_mutants", process::id()));
+    let mut survivors = 0;
+    for exercise in exercises {
+        progress!("Mutating the solution of {}...", exercise);
+        match mutate_one(exercise, &scratch) {
+            Ok(survived) => survivors += survived,
+            Err(e) => {
+                warn!("Could not mutate the solution of {}: {}", exercise, e);
+                survivors += 1;
+            }
+        }
+    }
+    let _ = fs::remove_dir_all(&scratch);
+    survivors == 0
+}
+
+fn mutate_one(exercise: &Exercise, scratch: &Path) -> io::Result<usize> {
+    if exercise.mode != Mode::Test {
+        return Err(io::Error::other(format!(
+            "only `test` exercises can be mutated, not `{}` ones",
+            exercise.mode
+        )));
+    }
+    if exercise.is_crate() {
+        return Err(io::Error::other("crate exercises cannot be mutated yet"));
+    }
+    let solution_path = selftest::solution_path(exercise);
+    let original = fs::read_to_string(&solution_path)?;
+    fs::create_dir_all(scratch)?;
+    let mut mutant = exercise.clone();
+    mutant.path = scratch.join(format!("{}.rs", exercise.name));
+
+    fs::write(&mutant.path, &original)?;
+    if test(&mutant) != Fate::Survived {
+        return Err(io::Error::other(format!(
+            "{} does not pass its own tests",
+            solution_path.display()
+        )));
+    }
+
+    let mutants = mutants(&original);
+    let (mut killed, mut survived, mut unviable) = (0, 0, 0);
+    for candidate in &mutants {
+        fs::write(&mutant.path, &candidate.source)?;
+        match test(&mutant) {
+            Fate::Killed => killed += 1,
+            Fate::Unviable => unviable += 1,
+            Fate::Survived => {
+                survived += 1;
+                warn!(
+                    "{}:{}: survived: {}",
+                    solution_path.display(),
+                    candidate.line,
+                    candidate.description
+                );
+                let old: Vec<&str> = original.lines().collect();
+                let new: Vec<&str> = candidate.source.lines().collect();
+                diff::print(&diff::diff(&old, &new));
+                println!();
+            }
+        }
+    }
+
+    let summary = format!(
+        "{} mutants of {}: {} killed, {} survived, {} did not compile.",
+        mutants.len(),
+        exercise.name,
+        killed,
+        survived,
+        unviable
+    );
+    if survived == 0 {
+        success!("{}", summary);
+    } else {
+        warn!("{}", summary);
+    }
+    Ok(survived)
+}
+
+/// Compiles and runs the tests of a mutated exercise.
+fn test(exercise: &Exercise) -> Fate {
+    let compiled = match exercise.compile() {
+        Ok(compiled) => compiled,
+        Err(_) => return Fate::Unviable,
+    };
+    let limits = Limits {
+        time: MUTANT_TIMEOUT,
+        ..exercise.limits
+    };
+    match sandbox::output(&compiled.command(), &limits, None) {
+        Ok((output, None)) if output.status.success() => Fate::Survived,
+        _ => Fate::Killed,
+    }
+}
+
+/// Generates every single-change mutant of `source` outside its tests:
+/// flipped comparisons and boolean operators, swapped bodies of neighbouring
+/// match arms, dropped `?` operators, and function bodies replaced with a
+/// trivial value of their return type.
+pub fn mutants(source: &str) -> Vec<Mutant> {
+    let code = code_mask(source);
+    let is_code = |start: usize, len: usize| code[start..start + len].iter().all(|&c| c);
+    let mut mutants = vec![];
+    let mut replace = |start: usize, end: usize, with: &str, description: String| {
+        let mut mutated = String::with_capacity(source.len());
+        mutated.push_str(&source[..start]);
+        mutated.push_str(with);
+        mutated.push_str(&source[end..]);
+        mutants.push(Mutant {
+            line: source[..start].matches('\n').count() + 1,
+            description,
+            source: mutated,
+        });
+    };
+
+    for (from, to) in OPERATORS {
+        for (start, _) in source.match_indices(from) {
+            if is_code(start, from.len()) {
+                let description = format!("replaced `{}` with `{}`", from.trim(), to.trim());
+                replace(start, start + from.len(), to, description);
+            }
+        }
+    }
+
+    let bytes = source.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        let lone = |j: Option<usize>| j.and_then(|j| bytes.get(j)) != Some(&b'?');
+        if b == b'?' && code[i] && lone(i.checked_sub(1)) && lone(Some(i + 1)) {
+            replace(i, i + 1, "", "dropped `?`".to_string());
+        }
+    }
+
+    for (start, _) in source.match_indices("match ") {
+        let word_start = start == 0 || !is_ident(bytes[start - 1]);
+        if !word_start || !is_code(start, 5) {
+            continue;
+        }
+        let arms = match_arms(source, &code, start + 5);
+        for pair in arms.windows(2) {
+            let (first, second) = (pair[0].clone(), pair[1].clone());
+            if source[first.clone()] == source[second.clone()] {
+                continue;
+            }
+            let swapped = format!(
+                "{}{}{}",
+                &source[second.clone()],
+                &source[first.end..second.start],
+                &source[first.clone()]
+            );
+            let description = format!(
+                "swapped match arm bodies `{}` and `{}`",
+                abbreviate(&source[first.clone()]),
+                abbreviate(&source[second.clone()])
+            );
+            replace(first.start, second.end, &swapped, description);
+        }
+    }
+
+    for (start, _) in source.match_indices("fn ") {
+        let word_start = start == 0 || !is_ident(bytes[start - 1]);
+        if !word_start || !is_code(start, 3) {
+            continue;
+        }
+        let (name, body, return_type) = match function(source, &code, start) {
+            Some(function) => function,
+            None => continue,
+        };
+        if name == "main" {
+            continue;
+        }
+        for value in default_values(&return_type) {
+            let replacement = match value {
+                "" => "{}".to_string(),
+                value => format!("{{ {} }}", value),
+            };
+            if squeeze(&source[body.clone()]) == squeeze(&replacement) {
+                continue;
+            }
+            let description = format!("replaced the body of `{}` with `{}`", name, replacement);
+            replace(body.start, body.end, &replacement, description);
+        }
+    }
+
+    mutants.sort_by_key(|m| m.line);
+    mutants
+}
+
+/// Splits the function whose `fn` keyword is at `start` into its name, the
+/// byte range of its body including braces, and its return type. Returns
+/// `None` for declarations without a body.
+fn function(source: &str, code: &[bool], start: usize) -> Option<(String, Range<usize>, String)> {
+    let name: String = source[start + 3..]
+        .trim_start()
+        .chars()
+        .take_while(|&c| c.is_alphanumeric() || c == '_')
+        .collect();
+    let params = start + source[start..].find('(')?;
+    let params_end = find_at_depth(source, code, params + 1, |s| s.starts_with(')'))?;
+    let open = find_at_depth(source, code, params_end + 1, |s| {
+        s.starts_with('{') || s.starts_with(';')
+    })?;
+    if !source[open..].starts_with('{') {
+        return None;
+    }
+    let close = find_at_depth(source, code, open + 1, |s| s.starts_with('}'))?;
+
+    let signature = source[params_end + 1..open].trim();
+    let signature = signature.split(" where ").next().unwrap_or_default();
+    let return_type = signature
+        .strip_prefix("->")
+        .map_or("()", str::trim)
+        .to_string();
+    Some((name, open..close + 1, return_type))
+}
+
+/// Values a function returning `return_type` could trivially return instead.
+fn default_values(return_type: &str) -> Vec<&'static str> {
+    const INTEGERS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    match return_type {
+        "()" => vec![""],
+        "bool" => vec!["true", "false"],
+        "String" => vec!["String::new()"],
+        ty if INTEGERS.contains(&ty) => vec!["0", "1"],
+        ty if ty.starts_with('&') && ty.ends_with(" str") || ty == "&str" => vec!["\"\""],
+        _ => vec![],
+    }
+}
+
+fn squeeze(text: &str) -> String {
+    text.split_whitespace().collect()
+}
+
+/// Returns the byte ranges of the arm bodies of the `match` whose scrutinee
+/// starts at `from`.
+fn match_arms(source: &str, code: &[bool], from: usize) -> Vec<Range<usize>> {
+    let bytes = source.as_bytes();
+    let mut arms = vec![];
+    let mut i = match (from..bytes.len()).find(|&i| code[i] && bytes[i] == b'{') {
+        Some(open) => open + 1,
+        None => return arms,
+    };
+
+    loop {
+        while i < bytes.len() && (bytes[i].is_ascii_whitespace() || !code[i]) {
+            i += 1;
+        }
+        if i >= bytes.len() || bytes[i] == b'}' {
+            return arms;
+        }
+        let arrow = match find_at_depth(source, code, i, |s| s.starts_with("=>")) {
+            Some(arrow) => arrow,
+            None => return arms,
+        };
+        let mut start = arrow + 2;
+        while start < bytes.len() && bytes[start].is_ascii_whitespace() {
+            start += 1;
+        }
+        let end = if bytes.get(start) == Some(&b'{') {
+            match find_at_depth(source, code, start + 1, |s| s.starts_with('}')) {
+                Some(close) => close + 1,
+                None => return arms,
+            }
+        } else {
+            match find_at_depth(source, code, start, |s| {
+                s.starts_with(',') || s.starts_with('}')
+            }) {
+                Some(end) => end,
+                None => return arms,
+            }
+        };
+        let body_end = start + source[start..end].trim_end().len();
+        arms.push(start..body_end);
+
+        i = end;
+        while i < bytes.len() && (bytes[i].is_ascii_whitespace() || !code[i]) {
+            i += 1;
+        }
+        if bytes.get(i) == Some(&b',') {
+            i += 1;
+        }
+    }
+}
+
+/// Finds the first code position at or after `from` where `matches` holds
+/// and brackets opened since `from` are balanced. Stops at a closing bracket
+/// that was opened before `from`.
+fn find_at_depth<F: Fn(&str) -> bool>(
+    source: &str,
+    code: &[bool],
+    from: usize,
+    matches: F,
+) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let mut depth = 0i32;
+    for i in from..bytes.len() {
+        if !code[i] {
+            continue;
+        }
+        if depth == 0 && matches(&source[i..]) {
+            return Some(i);
+        }
+        match bytes[i] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth -= 1;
+                if depth < 0 {
+                    return None;
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Marks each byte of `source` that is code outside of tests: not in a
+/// comment, a string or character literal, or a `#[test]` or `#[cfg(test)]`
+/// item.
+fn code_mask(source: &str) -> Vec<bool> {
+    let bytes = source.as_bytes();
+    let mut code = vec![true; bytes.len()];
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &source[i..];
+        let end = if rest.starts_with("//") {
+            rest.find('\n').map_or(bytes.len(), |n| i + n)
+        } else if rest.starts_with("/*") {
+            rest.find("*/").map_or(bytes.len(), |n| i + n + 2)
+        } else if rest.starts_with('"') {
+            string_end(bytes, i + 1)
+        } else if (rest.starts_with("r\"") || rest.starts_with("r#\""))
+            && (i == 0 || !is_ident(bytes[i - 1]))
+        {
+            let hashes = rest[1..].bytes().take_while(|&b| b == b'#').count();
+            let close = format!("\"{}", "#".repeat(hashes));
+            let open = i + 1 + hashes + 1;
+            source[open.min(bytes.len())..]
+                .find(&close)
+                .map_or(bytes.len(), |n| open + n + close.len())
+        } else if rest.starts_with('\'') {
+            char_end(rest).map_or(i + 1, |n| i + n)
+        } else {
+            i += rest.chars().next().map_or(1, char::len_utf8);
+            continue;
+        };
+        for c in &mut code[i..end] {
+            *c = false;
+        }
+        i = end.max(i + 1);
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+    let mut offset = 0;
+    let mut skip_until = 0;
+    for (n, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if n >= skip_until && (trimmed == "#[test]" || trimmed == "#[cfg(test)]") {
+            skip_until = protect::item_end(&lines, n);
+        }
+        if n < skip_until {
+            let end = (offset + line.len()).min(code.len());
+            for c in &mut code[offset..end] {
+                *c = false;
+            }
+        }
+        offset += line.len() + 1;
+    }
+    code
+}
+
+fn string_end(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Returns the length of the character literal at the start of `rest`, or
+/// `None` if it is a lifetime.
+fn char_end(rest: &str) -> Option<usize> {
+    let mut chars = rest.char_indices().skip(1);
+    match chars.next()? {
+        (_, '\\') => rest[2..].find('\'').map(|n| n + 3),
+        (_, _) => match chars.next()? {
+            (n, '\'') => Some(n + 1),
+            _ => None,
+        },
+    }
+}
+
+fn is_ident(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+fn abbreviate(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default().trim();
+    if line.len() < text.trim().len() || line.chars().count() > 30 {
+        let short: String = line.chars().take(27).collect();
+        format!("{}...", short)
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptions(source: &str) -> Vec<(usize, String)> {
+        mutants(source)
+            .into_iter()
+            .map(|m| (m.line, m.description))
+            .collect()
+    }
+
+    fn mutant<'a>(mutants: &'a [Mutant], description: &str) -> &'a str {
+        &mutants
+            .iter()
+            .find(|m| m.description == description)
+            .unwrap_or_else(|| panic!("no mutant {}", description))
+            .source
+    }
+
+    #[test]
+    fn flips_operators_and_replaces_bodies() {
+        let source = "fn small(a: i32) -> bool {\n    a == 1 && a < 2\n}\n";
+        assert_eq!(
+            descriptions(source),
+            vec![
+                (
+                    1,
+                    "replaced the body of `small` with `{ true }`".to_string()
+                ),
+                (
+                    1,
+                    "replaced the body of `small` with `{ false }`".to_string()
+                ),
+                (2, "replaced `==` with `!=`".to_string()),
+                (2, "replaced `<` with `>=`".to_string()),
+                (2, "replaced `&&` with `||`".to_string()),
+            ]
+        );
+        let mutants = mutants(source);
+        assert_eq!(
+            mutant(&mutants, "replaced `&&` with `||`"),
+            "fn small(a: i32) -> bool {\n    a == 1 || a < 2\n}\n"
+        );
+        assert_eq!(
+            mutant(&mutants, "replaced the body of `small` with `{ false }`"),
+            "fn small(a: i32) -> bool { false }\n"
+        );
+    }
+
+    #[test]
+    fn leaves_comments_strings_generics_and_tests_alone() {
+        let source = "fn words<'a>(s: &'a str) -> Vec<&'a str> {\n    // a < b\n    let _ = \"a == b\";\n    let _ = '<';\n    s.split(' ').collect::<Vec<_>>()\n}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn t() {\n        assert!(1 == 1 && true);\n    }\n}\n";
+        assert!(descriptions(source).is_empty());
+    }
+
+    #[test]
+    fn drops_lone_question_marks() {
+        let source = "fn parse(s: &str) -> Result<u8, std::num::ParseIntError> {\n    let n = s.parse::<u8>()?;\n    Ok(n)\n}\n";
+        assert_eq!(descriptions(source), vec![(2, "dropped `?`".to_string())]);
+        assert_eq!(mutants(source)[0].source, source.replace("()?;", "();"));
+    }
+
+    #[test]
+    fn swaps_neighbouring_match_arms() {
+        let source = "fn name(n: u8) -> &'static str {\n    match n {\n        0 => \"zero\",\n        1 => \"one\",\n        _ => \"one\",\n    }\n}\n";
+        let mutants = mutants(source);
+        let descriptions: Vec<&str> = mutants.iter().map(|m| m.description.as_str()).collect();
+        // The last two arms are the same, so swapping them changes nothing.
+        assert_eq!(
+            descriptions,
+            vec![
+                "replaced the body of `name` with `{ \"\" }`",
+                "swapped match arm bodies `\"zero\"` and `\"one\"`",
+            ]
+        );
+        assert_eq!(
+            mutant(
+                &mutants,
+                "swapped match arm bodies `\"zero\"` and `\"one\"`"
+            ),
+            source.replacen(
+                "0 => \"zero\",\n        1 => \"one\"",
+                "0 => \"one\",\n        1 => \"zero\"",
+                1
+            )
+        );
+    }
+
+    #[test]
+    fn skips_main_declarations_and_trivial_bodies() {
+        let source = "trait T {\n    fn f(&self) -> u32;\n}\n\nfn zero() -> u32 {\n    0\n}\n\nfn log() {\n    println!(\"hi\");\n}\n\nfn main() {\n    log();\n}\n";
+        assert_eq!(
+            descriptions(source),
+            vec![
+                (5, "replaced the body of `zero` with `{ 1 }`".to_string()),
+                (9, "replaced the body of `log` with `{}`".to_string()),
+            ]
+        );
+    }
+}
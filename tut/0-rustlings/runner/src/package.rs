@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+
+use crate::exercise::{CompiledExercise, Exercise, ExerciseOutput, Mode};
+use crate::explain;
+
+/// The manifest every crate exercise has at its root.
+pub const MANIFEST: &str = "Cargo.toml";
+
+/// The part of a `cargo --message-format=json` message the runner looks at.
+#[derive(Deserialize)]
+struct Message {
+    reason: String,
+    #[serde(default)]
+    executable: Option<PathBuf>,
+    #[serde(default)]
+    message: Option<Diagnostic>,
+}
+
+/// A compiler diagnostic carried by a `compiler-message`.
+#[derive(Deserialize)]
+struct Diagnostic {
+    level: String,
+    rendered: Option<String>,
+}
+
+/// Builds a crate exercise: a directory with a `Cargo.toml`, which may be a
+/// workspace and have build scripts, submodules in other files or
+/// integration tests.
+///
+/// The directory is copied to a scratch directory so that neither
+/// `Cargo.lock` nor `target/` end up in the student's tree, and built there
+/// with `cargo --offline`. `test` exercises only build their tests here;
+/// running the compiled exercise runs `cargo test`. `run` exercises run the
+/// first binary the package builds.
+pub fn build(exercise: &Exercise) -> Result<CompiledExercise, ExerciseOutput> {
+    let scratch = exercise.temp_binary();
+    let _ = fs::remove_dir_all(&scratch);
+    copy_dir(&exercise.path, &scratch)?;
+    let manifest = scratch.join(MANIFEST);
+
+    let mut cmd = match exercise.mode {
+        Mode::Test => {
+            let mut cmd = cargo("test", &manifest);
+            cmd.arg("--no-run");
+            cmd
+        }
+        _ => cargo("build", &manifest),
+    };
+    // The pinned nightly's cargo has no `json-render-diagnostics`, so the
+    // diagnostics come back as JSON on stdout and are rendered here.
+    let output = cmd.arg("--message-format=json").output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let messages: Vec<Message> = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    if !output.status.success() {
+        let _ = fs::remove_dir_all(&scratch);
+        let mut stderr = String::new();
+        for diagnostic in messages
+            .iter()
+            .filter_map(|message| message.message.as_ref())
+        {
+            if let Some(rendered) = &diagnostic.rendered {
+                stderr.push_str(&explain::colorize(&diagnostic.level, rendered));
+            }
+        }
+        stderr.push_str(&String::from_utf8_lossy(&output.stderr));
+        return Err(ExerciseOutput {
+            stdout: String::new(),
+            stderr,
+            status: output.status.code(),
+            violation: None,
+        });
+    }
+
+    if exercise.mode != Mode::Run {
+        return Ok(CompiledExercise::package(manifest, scratch));
+    }
+    let binary = messages
+        .into_iter()
+        .filter(|message| message.reason == "compiler-artifact")
+        .find_map(|message| message.executable);
+    match binary {
+        Some(binary) => Ok(CompiledExercise::in_scratch(binary, scratch)),
+        None => {
+            let _ = fs::remove_dir_all(&scratch);
+            Err(ExerciseOutput {
+                stdout: String::new(),
+                stderr: format!("{} has no binary to run.", exercise),
+                status: None,
+                violation: None,
+            })
+        }
+    }
+}
+
+/// Returns a `cargo <subcommand>` invocation for every package of the
+/// workspace at `manifest` that never touches the network, building into
+/// the `target/` next to `manifest`.
+pub fn cargo(subcommand: &str, manifest: &Path) -> Command {
+    let target = manifest.with_file_name("target");
+    let mut cmd = Command::new("cargo");
+    cmd.arg(subcommand)
+        .arg("--manifest-path")
+        .arg(manifest)
+        .args(["--offline", "--all", "--color", "always"])
+        .env("CARGO_TARGET_DIR", target);
+    cmd
+}
+
+/// Collects the `.rs` files under `dir`, recursively, leaving out build
+/// output.
+pub fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if !path.ends_with("target") {
+                rust_files(&path, files)?;
+            }
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Reads every file under `dir` except build output, keyed by its path
+/// relative to `dir`.
+pub fn read_tree(dir: &Path) -> io::Result<BTreeMap<PathBuf, Vec<u8>>> {
+    let mut files = BTreeMap::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(current) = dirs.pop() {
+        for entry in fs::read_dir(&current)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                if !path.ends_with("target") {
+                    dirs.push(path);
+                }
+            } else {
+                let relative = path.strip_prefix(dir).map_err(io::Error::other)?;
+                files.insert(relative.to_path_buf(), fs::read(&path)?);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Copies `from` to `to`, recursively, leaving out build output.
+pub fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        let dest = to.join(path.file_name().unwrap_or_default());
+        if path.is_dir() {
+            if !path.ends_with("target") {
+                copy_dir(&path, &dest)?;
+            }
+        } else {
+            fs::copy(&path, &dest)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// The git configuration key that records the commit the exercises were last
+/// handed out in, e.g. a tag the course moves with every update. Students
+/// committing their work does not move it, so it is preferred to the history
+/// of each file.
+pub const BASE_KEY: &str = "rustlings.base";
+
+/// Returns the contents of `path` as it was last handed out: in the recorded
+/// base commit if there is one, or else in the last commit that touched it.
+pub fn original_source(path: &Path) -> io::Result<String> {
+    let commit = base_commit(path)?;
+    let object = format!("{}:./{}", commit, path.display());
+    let source = git(&["show", &object])?;
+    Ok(source)
+}
+
+/// Writes the files under `dir` into `dest` as they were last handed out,
+/// i.e. a crate exercise as the student got it. Files the student added since
+/// are left out.
+pub fn original_tree(dir: &Path, dest: &Path) -> io::Result<()> {
+    let commit = base_commit(dir)?;
+    let files = git(&[
+        "ls-tree",
+        "-r",
+        "--name-only",
+        &commit,
+        "--",
+        &dir.to_string_lossy(),
+    ])?;
+    for file in files.lines().map(Path::new) {
+        let object = format!("{}:./{}", commit, file.display());
+        let source = git(&["show", &object])?;
+        let dest = dest.join(file.strip_prefix(dir).map_err(io::Error::other)?);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(dest, source)?;
+    }
+    Ok(())
+}
+
+/// Returns the git object id of `path` as it was last handed out, which
+/// changes whenever the handed-out file or directory does.
+pub fn original_id(path: &Path) -> io::Result<String> {
+    let commit = base_commit(path)?;
+    let object = format!("{}:./{}", commit, path.display());
+    let id = git(&["rev-parse", &object])?;
+    Ok(id.trim().to_string())
+}
+
+/// The commit whose version of `path` was handed out last.
+fn base_commit(path: &Path) -> io::Result<String> {
+    if let Ok(base) = git(&["config", "--get", BASE_KEY]) {
+        return Ok(base.trim().to_string());
+    }
+    let path = path.to_string_lossy();
+    let log = git(&["log", "-1", "--format=%H", "--", &path])?;
+    match log.lines().next() {
+        Some(commit) => Ok(commit.to_string()),
+        None => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} has never been committed to git", path),
+        )),
+    }
+}
+
+fn git(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(stderr.trim().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Where progress is kept, relative to `tut/0-rustlings`.
+pub const PROGRESS_FILE: &str = ".rustlings-progress.toml";
+
+/// What the runner remembers about each exercise, keyed by its `info.toml`
+/// name. Timestamps are seconds since the Unix epoch.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Progress {
+    #[serde(default)]
+    pub exercises: BTreeMap<String, Record>,
+}
+
+/// The history of a single exercise.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Record {
+    /// When the exercise was first checked.
+    pub started: Option<u64>,
+    /// When the exercise first passed with its `I AM NOT DONE` marker removed.
+    pub passed: Option<u64>,
+    /// How many times the exercise was checked before it passed.
+    #[serde(default)]
+    pub attempts: u32,
+    /// How many times a hint was asked for. Each time reveals the next hint
+    /// level, if there is one.
+    #[serde(default)]
+    pub hints: u32,
+}
+
+impl Progress {
+    /// Reads the progress file, or returns empty progress if there is none.
+    pub fn load() -> io::Result<Progress> {
+        if !Path::new(PROGRESS_FILE).exists() {
+            return Ok(Progress::default());
+        }
+        let text = fs::read_to_string(PROGRESS_FILE)?;
+        toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes the progress file.
+    pub fn save(&self) -> io::Result<()> {
+        let text =
+            toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(PROGRESS_FILE, text)
+    }
+
+    /// Returns the record of `name`, if it was ever checked or hinted.
+    pub fn get(&self, name: &str) -> Option<&Record> {
+        self.exercises.get(name)
+    }
+}
+
+impl Record {
+    /// Counts a check at `now`, until the exercise has passed.
+    fn attempt(&mut self, done: bool, now: u64) {
+        self.started.get_or_insert(now);
+        if self.passed.is_none() {
+            self.attempts += 1;
+            if done {
+                self.passed = Some(now);
+            }
+        }
+    }
+}
+
+/// Records that `name` was checked and whether it is now done.
+pub fn record_attempt(name: &str, done: bool) {
+    update(name, |record| record.attempt(done, now()));
+}
+
+/// Records that a hint of `name` was shown.
+pub fn record_hint(name: &str) {
+    update(name, |record| record.hints += 1);
+}
+
+fn update<F: FnOnce(&mut Record)>(name: &str, f: F) {
+    let result = Progress::load().and_then(|mut progress| {
+        f(progress.exercises.entry(name.to_string()).or_default());
+        progress.save()
+    });
+    if let Err(e) = result {
+        warn!("Could not update {}: {}", PROGRESS_FILE, e);
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Formats the current time as `YYYY-MM-DD_HH-MM-SS` in UTC, for file names.
+pub fn file_timestamp() -> String {
+    format_file_time(now())
+}
+
+fn format_file_time(secs: u64) -> String {
+    let time = format_time(secs).replace(' ', "_").replace(':', "-");
+    format!("{}-{:02}", time, secs % 60)
+}
+
+/// Formats a timestamp as `YYYY-MM-DD HH:MM` in UTC.
+pub fn format_time(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let (hour, minute) = ((secs % 86_400) / 3600, (secs % 3600) / 60);
+
+    // Howard Hinnant's days-to-civil algorithm.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year, month, day, hour, minute
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_times_in_utc() {
+        assert_eq!(format_time(0), "1970-01-01 00:00");
+        assert_eq!(format_time(951_782_400), "2000-02-29 00:00");
+        assert_eq!(format_time(951_868_799), "2000-02-29 23:59");
+        assert_eq!(format_time(1_709_251_199), "2024-02-29 23:59");
+        assert_eq!(format_time(1_735_689_599), "2024-12-31 23:59");
+        assert_eq!(format_time(4_107_542_400), "2100-03-01 00:00");
+    }
+
+    #[test]
+    fn formats_times_for_file_names() {
+        assert_eq!(format_file_time(1_735_689_599), "2024-12-31_23-59-59");
+        assert_eq!(format_file_time(5), "1970-01-01_00-00-05");
+    }
+
+    #[test]
+    fn counts_attempts_until_the_exercise_passes() {
+        let mut record = Record::default();
+        record.attempt(false, 10);
+        record.attempt(true, 20);
+        record.attempt(false, 30);
+        assert_eq!(record.started, Some(10));
+        assert_eq!(record.passed, Some(20));
+        assert_eq!(record.attempts, 2);
+    }
+}
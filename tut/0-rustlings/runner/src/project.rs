@@ -0,0 +1,196 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Serialize;
+use toml::Value;
+
+use crate::exercise::{Exercise, ExerciseList, Mode};
+use crate::package;
+
+/// Where the project description for rust-analyzer is written, relative to
+/// `tut/0-rustlings`. It holds the absolute path of the local sysroot, so it
+/// is not checked in.
+pub const PROJECT_FILE: &str = "rust-project.json";
+
+const EDITION: &str = "2018";
+
+/// The `rust-project.json` format rust-analyzer reads for projects that are
+/// not built by cargo.
+#[derive(Serialize)]
+struct Project {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sysroot: Option<String>,
+    crates: Vec<Crate>,
+}
+
+#[derive(Serialize)]
+struct Crate {
+    display_name: String,
+    root_module: PathBuf,
+    edition: String,
+    deps: Vec<Dep>,
+    cfg: Vec<String>,
+    is_workspace_member: bool,
+}
+
+#[derive(Serialize)]
+struct Dep {
+    #[serde(rename = "crate")]
+    index: usize,
+    name: String,
+}
+
+/// Writes `PROJECT_FILE` with a crate for every exercise, so that editors
+/// can check exercise files that have no `Cargo.toml`. `test` exercises are
+/// checked with `cfg(test)`. For crate exercises, the library, the binary and
+/// each integration test of the package are crates of their own.
+pub fn generate(list: &ExerciseList) -> io::Result<()> {
+    let mut crates = vec![];
+    for exercise in &list.exercises {
+        if exercise.is_crate() {
+            add_package(exercise, &mut crates)?;
+        } else {
+            crates.push(Crate {
+                display_name: exercise.name.clone(),
+                root_module: exercise.path.clone(),
+                edition: EDITION.to_string(),
+                deps: vec![],
+                cfg: cfg(exercise.mode == Mode::Test),
+                is_workspace_member: true,
+            });
+        }
+    }
+    let project = Project {
+        sysroot: sysroot(),
+        crates,
+    };
+    let json = serde_json::to_string_pretty(&project).map_err(io::Error::other)?;
+    fs::write(PROJECT_FILE, json + "\n")
+}
+
+/// Regenerates `PROJECT_FILE` if it is missing or older than `info.toml`,
+/// warning instead of failing if it cannot be written.
+pub fn refresh(list: &ExerciseList, info: &Path) {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    let stale = match (modified(Path::new(PROJECT_FILE)), modified(info)) {
+        (Some(project), Some(info)) => project < info,
+        _ => true,
+    };
+    if stale {
+        if let Err(e) = generate(list) {
+            warn!("Could not write {}: {}", PROJECT_FILE, e);
+        }
+    }
+}
+
+/// Reloads `info` and writes `PROJECT_FILE` from it, so that exercises added
+/// while `watch` or `tui` runs are described too.
+pub fn regenerate(info: &Path) -> io::Result<()> {
+    generate(&ExerciseList::load(info)?)
+}
+
+/// Adds the library, the binary and the integration tests of a crate
+/// exercise. Workspaces are not described; rust-analyzer can open their
+/// `Cargo.toml` directly.
+fn add_package(exercise: &Exercise, crates: &mut Vec<Crate>) -> io::Result<()> {
+    let manifest = fs::read_to_string(exercise.path.join(package::MANIFEST))?;
+    let manifest: Value =
+        toml::from_str(&manifest).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let package = match manifest.get("package") {
+        Some(package) => package,
+        None => return Ok(()),
+    };
+    let name = package
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or(&exercise.name)
+        .replace('-', "_");
+    let edition = package
+        .get("edition")
+        .and_then(Value::as_str)
+        .unwrap_or("2015")
+        .to_string();
+    let test = exercise.mode == Mode::Test;
+
+    let lib = exercise.path.join("src").join("lib.rs");
+    let lib = if lib.is_file() {
+        crates.push(Crate {
+            display_name: name.clone(),
+            root_module: lib,
+            edition: edition.clone(),
+            deps: vec![],
+            cfg: cfg(test),
+            is_workspace_member: true,
+        });
+        Some(crates.len() - 1)
+    } else {
+        None
+    };
+    let deps = || -> Vec<Dep> {
+        lib.map(|index| Dep {
+            index,
+            name: name.clone(),
+        })
+        .into_iter()
+        .collect()
+    };
+
+    let main = exercise.path.join("src").join("main.rs");
+    if main.is_file() {
+        crates.push(Crate {
+            display_name: format!("{} (bin)", name),
+            root_module: main,
+            edition: edition.clone(),
+            deps: deps(),
+            cfg: cfg(test),
+            is_workspace_member: true,
+        });
+    }
+
+    let mut tests = vec![];
+    let dir = exercise.path.join("tests");
+    if dir.is_dir() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "rs") {
+                tests.push(path);
+            }
+        }
+    }
+    tests.sort();
+    for test in tests {
+        let stem = test.file_stem().unwrap_or_default().to_string_lossy();
+        crates.push(Crate {
+            display_name: format!("{} (test {})", name, stem),
+            root_module: test,
+            edition: edition.clone(),
+            deps: deps(),
+            cfg: cfg(true),
+            is_workspace_member: true,
+        });
+    }
+    Ok(())
+}
+
+fn cfg(test: bool) -> Vec<String> {
+    if test {
+        vec!["test".to_string()]
+    } else {
+        vec![]
+    }
+}
+
+/// The sysroot of the `rustc` exercises are compiled with, from which
+/// rust-analyzer finds the standard library sources.
+fn sysroot() -> Option<String> {
+    let output = Command::new("rustc")
+        .args(["--print", "sysroot"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -0,0 +1,598 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use crate::budget::Budget;
+use crate::diff::{self, Line};
+use crate::exercise::{self, Exercise};
+use crate::package;
+use crate::pristine;
+
+const MARKER_REGEX: &str = r"(?i)^\s*//\s*(?:do not|don't)\s+(?:modify|change)\s+(this|the following)\s+(?:(\w+)\s+)?(function|module|definition|lines?)\b";
+const INNER_TYPE_REGEX: &str =
+    r"(?i)^\s*//\s*(?:do not|don't)\s+(?:modify|change)\s+the\s+inner\s+type\s+(.+?)\.?\s*$";
+const NO_USE_REGEX: &str = r"(?i)^\s*//\s*(?:do not|don't)\s+`?use`?\s+any\s+items\b";
+const ITEM_REGEX: &str = r"^\s*(?:pub(?:\([^)]*\))?\s+)?(struct|enum|union|type|trait|fn)\s+(\w+)";
+const USE_REGEX: &str = r"^\s*(?:pub(?:\([^)]*\))?\s+)?use\s";
+const EDITABLE_REGEX: &str =
+    r"(?i)^\s*//\s*(?:change|edit|fix)\s+(this|the following)\s+(?:(\w+)\s+)?lines?\b";
+const OTHER_FILES_REGEX: &str =
+    r"(?i)^\s*//.*\b(?:do not|don't)\s+(?:modify|change)\s+the\s+other\s+files\b";
+const QUOTED_REGEX: &str = r"`([^`]+)`";
+
+/// A part of the pristine exercise the student's version must keep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// The file of a crate exercise the region is in, relative to the crate.
+    pub file: Option<PathBuf>,
+    /// The 1-based line number of the region in the pristine exercise, or
+    /// `None` for a whole file or a line required by `info.toml`.
+    pub line: Option<usize>,
+    /// Why the region is protected, usually the marker comment itself.
+    pub reason: String,
+    pub rule: Rule,
+}
+
+/// What the student's version must do to keep a region intact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// The lines must appear verbatim where they were, between the lines
+    /// around them. Without a line number, they are the non-blank lines of a
+    /// whole file, which must keep all of them but its `I AM NOT DONE` marker.
+    Verbatim(Vec<String>),
+    /// Some line must match the pattern, described by `hint`.
+    Matches { pattern: String, hint: String },
+    /// No line may match the pattern unless the pristine exercise has it too.
+    Forbids { pattern: String, hint: String },
+}
+
+impl Region {
+    /// Where the region starts: a line, `file:line` or a file, or `None` for
+    /// a line required by `info.toml`.
+    pub fn location(&self) -> Option<String> {
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => Some(format!("{}:{}", file.display(), line)),
+            (Some(file), None) => Some(file.display().to_string()),
+            (None, Some(line)) => Some(line.to_string()),
+            (None, None) => None,
+        }
+    }
+
+    /// Whether `current` keeps the region intact. `kept` maps each line of
+    /// `original` to the line of `current` a diff keeps it as.
+    fn holds(&self, original: &[&str], current: &[&str], kept: &[Option<usize>]) -> bool {
+        match &self.rule {
+            Rule::Verbatim(lines) => match self.line {
+                Some(line) => {
+                    in_place(lines, line - 1, current, kept)
+                        && !duplicated(lines, original, current)
+                }
+                None => current.iter().copied().filter(significant).eq(lines.iter()),
+            },
+            Rule::Matches { pattern, .. } => match Regex::new(pattern) {
+                Ok(re) => current.iter().any(|line| re.is_match(line)),
+                Err(_) => true,
+            },
+            Rule::Forbids { pattern, .. } => match Regex::new(pattern) {
+                Ok(re) => current
+                    .iter()
+                    .all(|line| !re.is_match(line) || original.contains(line)),
+                Err(_) => true,
+            },
+        }
+    }
+}
+
+/// Whether `lines`, found at `start` in the original, appear verbatim in
+/// `current` between the lines the diff keeps just before and after them, so
+/// that a copy elsewhere does not stand in for an edited original.
+fn in_place(lines: &[String], start: usize, current: &[&str], kept: &[Option<usize>]) -> bool {
+    let after = kept[..start.min(kept.len())]
+        .iter()
+        .rev()
+        .find_map(|&k| k)
+        .map_or(0, |k| k + 1);
+    let before = kept
+        .get(start + lines.len()..)
+        .and_then(|rest| rest.iter().find_map(|&k| k))
+        .unwrap_or(current.len());
+    before >= after
+        && current[after..before]
+            .windows(lines.len())
+            .any(|window| window.iter().eq(lines.iter()))
+}
+
+/// Whether the first line of code in `lines` appears more often in `current`
+/// than in `original`, as it does when a protected item is copied and the
+/// copy is edited instead.
+fn duplicated(lines: &[String], original: &[&str], current: &[&str]) -> bool {
+    let first = lines.iter().find(|line| {
+        let line = line.trim();
+        !line.is_empty() && !line.starts_with("#[") && !line.starts_with("//")
+    });
+    match first {
+        Some(first) => {
+            let count = |lines: &[&str]| lines.iter().filter(|line| *line == first).count();
+            count(current) > count(original)
+        }
+        None => false,
+    }
+}
+
+/// Maps each line of `original` to the line of `current` a diff of the two
+/// keeps it as, if any.
+fn kept_lines(original: &[&str], current: &[&str]) -> Vec<Option<usize>> {
+    let mut kept = Vec::with_capacity(original.len());
+    let mut j = 0;
+    for line in diff::diff(original, current) {
+        match line {
+            Line::Same(_) => {
+                kept.push(Some(j));
+                j += 1;
+            }
+            Line::Removed(_) => kept.push(None),
+            Line::Added(_) => j += 1,
+        }
+    }
+    kept
+}
+
+/// Finds the protected regions of a pristine exercise.
+///
+/// A region is either the item or lines following a "Do not modify this
+/// function." style comment, the type named by a "Do not modify the inner
+/// type &'a T." comment, the imports a "Do not `use` any items." comment
+/// rules out or, in exercises with a diff budget, a whole `#[test]` function
+/// but for the lines a "Fix the following two lines." comment in it leaves to
+/// the student.
+pub fn regions(original: &str) -> Vec<Region> {
+    let lines: Vec<&str> = original.lines().collect();
+    let marker = Regex::new(MARKER_REGEX).unwrap();
+    let inner_type = Regex::new(INNER_TYPE_REGEX).unwrap();
+    let no_use = Regex::new(NO_USE_REGEX).unwrap();
+    let item = Regex::new(ITEM_REGEX).unwrap();
+
+    let mut regions = vec![];
+    for (i, line) in lines.iter().enumerate() {
+        if no_use.is_match(line) {
+            regions.push(Region {
+                file: None,
+                line: Some(i + 1),
+                reason: line.trim().to_string(),
+                rule: Rule::Forbids {
+                    pattern: USE_REGEX.to_string(),
+                    hint: "Remove the `use` declarations you added.".to_string(),
+                },
+            });
+            continue;
+        }
+        if let Some(caps) = inner_type.captures(line) {
+            let start = next_code_line(&lines, i + 1);
+            if let Some(head) = start.and_then(|start| item.captures(lines[start])) {
+                let ty = caps[1].trim_end_matches('.');
+                regions.push(Region {
+                    file: None,
+                    line: start.map(|start| start + 1),
+                    reason: line.trim().to_string(),
+                    rule: Rule::Matches {
+                        pattern: format!(
+                            r"\b{}\s+{}\b.*[(:,]\s*{}\s*[),}}]",
+                            &head[1],
+                            &head[2],
+                            regex::escape(ty)
+                        ),
+                        hint: format!("`{} {}` must still contain `{}`.", &head[1], &head[2], ty),
+                    },
+                });
+            }
+            continue;
+        }
+        let caps = match marker.captures(line) {
+            Some(caps) => caps,
+            None => continue,
+        };
+        let start = match next_code_line(&lines, i + 1) {
+            Some(start) => start,
+            None => continue,
+        };
+        let end = match &caps[3] {
+            "line" | "lines" => {
+                let count = caps.get(2).and_then(|n| count(n.as_str())).unwrap_or(1);
+                (start + count).min(lines.len())
+            }
+            _ => item_end(&lines, start),
+        };
+        regions.push(region(&lines, start, end, line.trim()));
+    }
+
+    if Budget::parse(original).is_some() {
+        regions.extend(tests(&lines));
+    }
+    regions
+}
+
+/// Returns the regions of `original` and the lines `exercise` requires in
+/// `info.toml` that `current` does not keep intact.
+pub fn violations(exercise: &Exercise, original: &str, current: &str) -> Vec<Region> {
+    let required = exercise.required.iter().map(|required| Region {
+        file: None,
+        line: None,
+        reason: required.reason.clone(),
+        rule: Rule::Matches {
+            pattern: required.pattern.clone(),
+            hint: format!("Some line must match `{}`.", required.pattern),
+        },
+    });
+    let all = regions(original).into_iter().chain(required).collect();
+    broken(all, original, current)
+}
+
+/// Returns the regions of a crate exercise's pristine files under `original`
+/// that its files under `current` do not keep intact. A "do not change the
+/// other files" comment in any of them protects every file it does not name
+/// in backquotes as a whole; the named files keep their own regions.
+pub fn crate_violations(original: &Path, current: &Path) -> io::Result<Vec<Region>> {
+    let originals = package::read_tree(original)?;
+    let currents = package::read_tree(current)?;
+    let text = |bytes: &[u8]| String::from_utf8_lossy(bytes).into_owned();
+
+    let other_files = Regex::new(OTHER_FILES_REGEX).unwrap();
+    let quoted = Regex::new(QUOTED_REGEX).unwrap();
+    let mut marker = None;
+    for source in originals.values().map(|bytes| text(bytes)) {
+        if let Some(line) = source.lines().find(|line| other_files.is_match(line)) {
+            let named: Vec<PathBuf> = quoted
+                .captures_iter(line)
+                .map(|caps| PathBuf::from(&caps[1]))
+                .collect();
+            marker = Some((line.trim().to_string(), named));
+        }
+    }
+
+    let mut violations = vec![];
+    for (file, original) in &originals {
+        let original = text(original);
+        let current = currents.get(file).map_or_else(String::new, |c| text(c));
+        let all = match &marker {
+            Some((reason, named)) if !named.contains(file) => vec![Region {
+                file: None,
+                line: None,
+                reason: reason.clone(),
+                rule: Rule::Verbatim(
+                    original
+                        .lines()
+                        .filter(significant)
+                        .map(|line| line.to_string())
+                        .collect(),
+                ),
+            }],
+            _ => regions(&original),
+        };
+        violations.extend(
+            broken(all, &original, &current)
+                .into_iter()
+                .map(|region| Region {
+                    file: Some(file.clone()),
+                    ..region
+                }),
+        );
+    }
+    Ok(violations)
+}
+
+/// Whether a line of a file protected as a whole counts: blank lines and the
+/// `I AM NOT DONE` marker do not.
+fn significant(line: &&str) -> bool {
+    !line.trim().is_empty() && !exercise::is_done_marker(line)
+}
+
+fn broken(regions: Vec<Region>, original: &str, current: &str) -> Vec<Region> {
+    let original: Vec<&str> = original.lines().collect();
+    let current: Vec<&str> = current.lines().collect();
+    let kept = kept_lines(&original, &current);
+    regions
+        .into_iter()
+        .filter(|region| !region.holds(&original, &current, &kept))
+        .collect()
+}
+
+/// Compares an exercise against the protected regions of its pristine version.
+pub fn check(exercise: &Exercise) -> io::Result<Vec<Region>> {
+    if exercise.is_crate() {
+        let scratch = exercise.temp_binary().with_extension("pristine");
+        let _ = fs::remove_dir_all(&scratch);
+        let result = pristine::original_tree(&exercise.path, &scratch)
+            .and_then(|()| crate_violations(&scratch, &exercise.path));
+        let _ = fs::remove_dir_all(&scratch);
+        return result;
+    }
+    let original = pristine::original_source(&exercise.path)?;
+    let current = fs::read_to_string(&exercise.path)?;
+    Ok(violations(exercise, &original, &current))
+}
+
+/// Checks that an exercise's protected regions are intact, printing any that
+/// were edited. Exercises whose pristine version cannot be found are let through.
+pub fn untouched(exercise: &Exercise) -> bool {
+    let violations = match check(exercise) {
+        Ok(violations) => violations,
+        Err(e) => {
+            warn!("Skipping the protected regions of {}: {}", exercise, e);
+            return true;
+        }
+    };
+    if violations.is_empty() {
+        return true;
+    }
+    print_violations(exercise, &violations);
+    false
+}
+
+/// Prints the protected regions of `exercise` that were edited and what they
+/// must be restored to.
+pub fn print_violations(exercise: &Exercise, violations: &[Region]) {
+    warn!("{} changes code that must not be modified!", exercise);
+    for region in violations {
+        println!();
+        let path = match &region.file {
+            Some(file) => exercise.path.join(file),
+            None => exercise.path.clone(),
+        };
+        match region.line {
+            Some(line) => println!("{}:{}: {}", path.display(), line, region.reason),
+            None => println!("{}: {}", path.display(), region.reason),
+        }
+        match &region.rule {
+            Rule::Verbatim(_) if region.line.is_none() => {
+                println!("Undo your changes to this file.")
+            }
+            Rule::Verbatim(lines) => {
+                println!("Restore these lines exactly as they were:");
+                for line in lines {
+                    println!("    {}", line);
+                }
+            }
+            Rule::Matches { hint, .. } | Rule::Forbids { hint, .. } => println!("{}", hint),
+        }
+    }
+    println!();
+}
+
+fn region(lines: &[&str], start: usize, end: usize, reason: &str) -> Region {
+    Region {
+        file: None,
+        line: Some(start + 1),
+        reason: reason.to_string(),
+        rule: Rule::Verbatim(lines[start..end].iter().map(|l| l.to_string()).collect()),
+    }
+}
+
+/// Every `#[test]` function, attributes included, in pieces around the lines
+/// a "Fix the following two lines." comment in it leaves to the student.
+fn tests(lines: &[&str]) -> Vec<Region> {
+    const REASON: &str = "Do not modify the tests.";
+    let editable = Regex::new(EDITABLE_REGEX).unwrap();
+    let mut regions = vec![];
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].trim() != "#[test]" {
+            i += 1;
+            continue;
+        }
+        let end = item_end(lines, i);
+        let mut start = i;
+        let mut j = i;
+        while j < end {
+            let caps = match editable.captures(lines[j]) {
+                Some(caps) => caps,
+                None => {
+                    j += 1;
+                    continue;
+                }
+            };
+            let from = next_code_line(lines, j + 1).map_or(end, |from| from.min(end));
+            let count = caps.get(2).and_then(|n| count(n.as_str())).unwrap_or(1);
+            regions.push(region(lines, start, from, REASON));
+            start = (from + count).min(end);
+            j = start;
+        }
+        if start < end {
+            regions.push(region(lines, start, end, REASON));
+        }
+        i = end;
+    }
+    regions
+}
+
+fn next_code_line(lines: &[&str], from: usize) -> Option<usize> {
+    (from..lines.len()).find(|&i| {
+        let line = lines[i].trim();
+        !line.is_empty() && !line.starts_with("//")
+    })
+}
+
+/// Returns the index one past the last line of the item starting at `start`:
+/// attributes, then either a `;`-terminated line or a balanced `{ ... }` block.
+pub fn item_end(lines: &[&str], start: usize) -> usize {
+    let mut depth = 0i32;
+    let mut opened = false;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        let code = line.split("//").next().unwrap();
+        for c in code.chars() {
+            match c {
+                '{' => {
+                    depth += 1;
+                    opened = true;
+                }
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        let attribute = code.trim_start().starts_with("#[");
+        if !attribute && depth <= 0 && (opened || code.trim_end().ends_with(';')) {
+            return i + 1;
+        }
+    }
+    lines.len()
+}
+
+fn count(word: &str) -> Option<usize> {
+    const WORDS: &[&str] = &[
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    word.parse().ok().or_else(|| {
+        let word = word.to_lowercase();
+        WORDS.iter().position(|w| *w == word).map(|i| i + 1)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    const ORIGINAL: &str = "// FIXME: Make me pass! Diff budget: 25 lines.
+
+// I AM NOT DONE
+
+#[derive(Debug)]
+enum Duration {
+    Seconds(u32),
+    Minutes(u16),
+}
+
+#[test]
+fn traits() {
+    assert_eq!(Duration::Seconds(120), Duration::Minutes(2));
+    assert_eq!(Duration::Seconds(420), Duration::Minutes(7));
+}
+";
+
+    fn edited(original: &str, current: &str) -> Vec<String> {
+        let exercise: Exercise =
+            toml::from_str("name = \"a\"\npath = \"a.rs\"\nmode = \"test\"").unwrap();
+        violations(&exercise, original, current)
+            .into_iter()
+            .map(|region| format!("{}: {}", region.location().unwrap(), region.reason))
+            .collect()
+    }
+
+    #[test]
+    fn lets_code_be_added_around_tests() {
+        let current = ORIGINAL
+            .replace("// I AM NOT DONE\n", "")
+            .replace(
+                "#[test]\nfn traits",
+                "impl PartialEq for Duration {\n    fn eq(&self, _: &Duration) -> bool {\n        true\n    }\n}\n\n#[test]\nfn mine() {}\n\n#[test]\nfn traits",
+            );
+        assert_eq!(edited(ORIGINAL, &current), Vec::<String>::new());
+    }
+
+    #[test]
+    fn protects_whole_tests() {
+        let current = ORIGINAL
+            .replace("#[derive(Debug)]", "#[derive(Debug, PartialEq)]")
+            .replace("fn traits() {\n", "fn traits() {\n    return;\n");
+        assert_eq!(
+            edited(ORIGINAL, &current),
+            vec!["11: Do not modify the tests."]
+        );
+    }
+
+    #[test]
+    fn ignores_copies_of_tests_elsewhere() {
+        let test = &ORIGINAL[ORIGINAL.find("#[test]").unwrap()..];
+        let current = ORIGINAL.replace(
+            test,
+            &format!(
+                "#[cfg(any())]\nmod copy {{\n{}}}\n\n{}",
+                test,
+                test.replace("fn traits() {\n", "fn traits() {\n    return;\n")
+            ),
+        );
+        assert_eq!(
+            edited(ORIGINAL, &current),
+            vec!["11: Do not modify the tests."]
+        );
+    }
+
+    #[test]
+    fn notices_moved_assertions() {
+        let assertion = "    assert_eq!(Duration::Seconds(420), Duration::Minutes(7));\n";
+        let current = ORIGINAL.replace(assertion, "").replace(
+            "#[test]",
+            &format!("fn unused() {{\n{}}}\n\n#[test]", assertion),
+        );
+        assert_eq!(
+            edited(ORIGINAL, &current),
+            vec!["11: Do not modify the tests."]
+        );
+    }
+
+    #[test]
+    fn leaves_marked_lines_of_tests_to_the_student() {
+        let original = "// FIXME: Make me pass! Diff budget: 2 lines.
+
+#[test]
+fn ufcs() {
+    // Fix the following two lines.
+    let x = dummy.foo();
+    let y = dummy.foo();
+    assert_eq!(x, 1);
+    assert_eq!(y, 2);
+}
+";
+        let fixed = original
+            .replace("let x = dummy.foo()", "let x = Foo::foo(&dummy)")
+            .replace("let y = dummy.foo()", "let y = FooToo::foo(&dummy)");
+        assert_eq!(edited(original, &fixed), Vec::<String>::new());
+        let cheat = original.replace("    assert_eq!(y, 2);\n", "");
+        assert_eq!(
+            edited(original, &cheat),
+            vec!["8: Do not modify the tests."]
+        );
+    }
+
+    #[test]
+    fn protects_the_other_files_of_crates() {
+        let dir = env::temp_dir().join(format!("rustlings_{}_protect", process::id()));
+        let write = |crate_dir: &str, file: &str, source: &str| {
+            let path = dir.join(crate_dir).join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        };
+        let main = "// The fix belongs in `src/config.rs`; do not change the other files.\n\n// I AM NOT DONE\n\nmod config;\n\nfn main() {}\n";
+        let config = "pub struct Config {\n    port: u16,\n}\n";
+        for crate_dir in &["original", "current"] {
+            write(crate_dir, "src/main.rs", main);
+            write(crate_dir, "src/config.rs", config);
+            write(crate_dir, "src/server.rs", "pub fn start() {}\n");
+        }
+        write(
+            "current",
+            "src/main.rs",
+            &main.replace("// I AM NOT DONE\n", ""),
+        );
+        write(
+            "current",
+            "src/config.rs",
+            &config.replace("port", "pub port"),
+        );
+        let done = crate_violations(&dir.join("original"), &dir.join("current"));
+        write("current", "src/server.rs", "pub fn start() {\n}\n");
+        let edited = crate_violations(&dir.join("original"), &dir.join("current"));
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(done.unwrap(), vec![]);
+        let edited = edited.unwrap();
+        assert_eq!(edited.len(), 1);
+        assert_eq!(edited[0].location(), Some("src/server.rs".to_string()));
+        assert_eq!(
+            edited[0].reason,
+            "// The fix belongs in `src/config.rs`; do not change the other files."
+        );
+    }
+}
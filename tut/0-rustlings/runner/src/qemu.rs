@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::exercise::{CompiledExercise, Exercise, ExerciseOutput, Limits};
+use crate::sandbox;
+use crate::xbuild;
+
+const BOOT_STUB: &str = include_str!("../stub/boot.rs");
+const LINKER_SCRIPT: &str = include_str!("../stub/layout.ld");
+
+/// Where `bin/build-qemu.sh` leaves QEMU, relative to `tut/0-rustlings`,
+/// tried before `qemu-system-aarch64` on the `PATH`.
+const QEMU_CANDIDATES: &[&str] = &[
+    "../../ext/qemu-system-aarch64",
+    "../../ext/qemu/build/aarch64-softmmu/qemu-system-aarch64",
+    "../../ext/qemu/build/qemu-system-aarch64",
+];
+
+/// Cross-compiles a `qemu` exercise into a bootable aarch64 ELF image.
+///
+/// The exercise is `include!`d into the boot stub in `stub/boot.rs`, whose
+/// scratch crate is built with `cargo xrustc` and linked at the raspi3 load
+/// address by `stub/layout.ld`.
+pub fn build_image(exercise: &Exercise) -> Result<CompiledExercise, ExerciseOutput> {
+    let root = fs::canonicalize(&exercise.path)?;
+    let scratch = exercise.temp_binary();
+    let bin = format!("[[bin]]\nname = \"{}\"\npath = \"boot.rs\"", exercise.name);
+    let manifest = xbuild::write_manifest(exercise, &scratch, &bin)?;
+    let layout = scratch.join("layout.ld");
+    fs::write(
+        scratch.join("boot.rs"),
+        BOOT_STUB.replace("{exercise}", &root.display().to_string()),
+    )?;
+    fs::write(&layout, LINKER_SCRIPT)?;
+
+    let image = xbuild::artifact_dir(exercise).join(&exercise.name);
+    let compiled = CompiledExercise::in_scratch(image, scratch);
+    let output = xbuild::cargo("xrustc", exercise, &manifest)
+        .args(["--bin", &exercise.name, "--", "-C"])
+        .arg(format!("link-arg=-T{}", layout.display()))
+        .output()?;
+    if output.status.success() {
+        Ok(compiled)
+    } else {
+        Err(output.into())
+    }
+}
+
+/// Boots a compiled `qemu` exercise headless and captures its serial output.
+/// The exit code is the one the stub passes to semihosting: 0 once `main`
+/// returns, 101 on a panic.
+pub fn boot(exercise: &Exercise, image: &Path) -> ExerciseOutput {
+    let qemu = match find_qemu() {
+        Some(qemu) => qemu,
+        None => {
+            return ExerciseOutput {
+                stdout: String::new(),
+                stderr: "qemu-system-aarch64 not found; run bin/build-qemu.sh".to_string(),
+                status: None,
+                violation: None,
+            }
+        }
+    };
+
+    // QEMU itself needs more memory and CPU time than any exercise binary,
+    // so only the time it may take and its output are limited.
+    let limits = Limits {
+        cpu: None,
+        memory: None,
+        ..exercise.limits
+    };
+    let mut cmd = Command::new(qemu);
+    cmd.args(["-M", "raspi3", "-display", "none", "-serial", "stdio"])
+        .arg("-semihosting")
+        .arg("-kernel")
+        .arg(image);
+    match sandbox::output(&cmd, &limits, None) {
+        Ok((output, violation)) => {
+            let mut output = ExerciseOutput::from(output);
+            output.violation = violation;
+            output
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// Returns the QEMU `bin/build-qemu.sh` built, or else the one on the `PATH`.
+pub fn find_qemu() -> Option<PathBuf> {
+    QEMU_CANDIDATES
+        .iter()
+        .map(PathBuf::from)
+        .find(|path| path.is_file())
+        .or_else(|| {
+            Command::new("qemu-system-aarch64")
+                .arg("--version")
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|_| PathBuf::from("qemu-system-aarch64"))
+        })
+}
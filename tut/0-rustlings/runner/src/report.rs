@@ -0,0 +1,558 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::budget;
+use crate::exercise::{Exercise, Mode, State};
+use crate::expect;
+use crate::hidden::{self, HiddenTests};
+use crate::pristine;
+use crate::protect;
+use crate::sandbox::Violation;
+
+/// The machine-readable formats `verify --format` can emit.
+pub const FORMATS: &[&str] = &["json", "junit", "tap"];
+
+/// How far an exercise got, from the first check it failed.
+#[derive(Serialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Passed,
+    /// Everything passes, but the exercise is still marked `I AM NOT DONE`.
+    Pending,
+    CompileError,
+    TestFailure,
+    WrongOutput,
+    HiddenTestFailure,
+    ProtectedRegion,
+    OverBudget,
+    TimeLimit,
+    CpuLimit,
+    MemoryLimit,
+    OutputLimit,
+}
+
+impl Outcome {
+    /// Describes the outcome as a predicate, e.g. "fails to compile".
+    pub fn describe(self) -> &'static str {
+        match self {
+            Outcome::Passed => "passed",
+            Outcome::Pending => "is still marked I AM NOT DONE",
+            Outcome::CompileError => "fails to compile",
+            Outcome::TestFailure => "fails its tests",
+            Outcome::WrongOutput => "prints the wrong output",
+            Outcome::HiddenTestFailure => "fails hidden tests",
+            Outcome::ProtectedRegion => "changes code that must not be modified",
+            Outcome::OverBudget => "is over its diff budget",
+            Outcome::TimeLimit => "runs out of time",
+            Outcome::CpuLimit => "runs out of CPU time",
+            Outcome::MemoryLimit => "runs out of memory",
+            Outcome::OutputLimit => "prints too much output",
+        }
+    }
+}
+
+impl From<Violation> for Outcome {
+    fn from(violation: Violation) -> Outcome {
+        match violation {
+            Violation::Time(_) => Outcome::TimeLimit,
+            Violation::Cpu(_) => Outcome::CpuLimit,
+            Violation::Memory(_) => Outcome::MemoryLimit,
+            Violation::Output(_) => Outcome::OutputLimit,
+        }
+    }
+}
+
+/// The diff budget of an exercise and how much of it was used.
+#[derive(Serialize, Debug)]
+pub struct BudgetStatus {
+    pub allowed: String,
+    pub used: String,
+    pub within: bool,
+}
+
+/// A protected region that was edited, or a required line that is missing.
+#[derive(Serialize, Debug)]
+pub struct EditedRegion {
+    /// A line, `file:line` or a file of a crate exercise, or `None` for a line
+    /// required by `info.toml`.
+    pub location: Option<String>,
+    pub reason: String,
+}
+
+/// Everything `verify` found out about a single exercise.
+#[derive(Serialize, Debug)]
+pub struct ExerciseReport {
+    pub name: String,
+    pub path: PathBuf,
+    pub section: String,
+    pub mode: Mode,
+    pub outcome: Outcome,
+    /// Compiler or clippy output, without colors, if compiling failed.
+    pub diagnostics: Option<String>,
+    pub failing_tests: Vec<String>,
+    /// How each run of a `run` or `qemu` exercise differed from what was
+    /// expected.
+    pub output_mismatches: Vec<String>,
+    /// The limit a run of the exercise was stopped for exceeding, if any.
+    pub limit_exceeded: Option<String>,
+    /// `None` if the exercise has no hidden tests or this is not a grading
+    /// run.
+    pub hidden_tests: Option<HiddenTests>,
+    pub protected_regions: Vec<EditedRegion>,
+    /// `None` if the exercise has no diff budget or its pristine version
+    /// cannot be found.
+    pub diff_budget: Option<BudgetStatus>,
+    /// Why the protected regions and the diff budget went unchecked, if the
+    /// pristine version of the exercise could not be found.
+    pub unchecked: Option<String>,
+}
+
+/// Checks a single exercise without printing anything. Its hidden tests are
+/// only run when grading, from `hidden_dir`.
+pub fn evaluate(exercise: &Exercise, hidden_dir: Option<&Path>) -> ExerciseReport {
+    let original = original_source(exercise);
+    let mut report = evaluate_against(
+        exercise,
+        original.as_ref().ok().and_then(Option::as_deref),
+        hidden_dir,
+    );
+    report.unchecked = original.err();
+    report
+}
+
+/// Checks a single exercise without printing anything, comparing it against
+/// `original` for protected regions and the diff budget. Neither is checked
+/// without an `original`, except that crate exercises are always compared
+/// against their pristine files in git.
+pub fn evaluate_against(
+    exercise: &Exercise,
+    original: Option<&str>,
+    hidden_dir: Option<&Path>,
+) -> ExerciseReport {
+    check(exercise, original, hidden_dir, false)
+}
+
+/// Checks a single exercise like `evaluate` does without hidden tests, and
+/// prints what it gets wrong for the student along the way.
+pub fn evaluate_verbosely(exercise: &Exercise) -> ExerciseReport {
+    let original = original_source(exercise);
+    if let Err(e) = &original {
+        warn!(
+            "Skipping the protected regions and diff budget of {}: {}",
+            exercise, e
+        );
+    }
+    let mut report = check(
+        exercise,
+        original.as_ref().ok().and_then(Option::as_deref),
+        None,
+        true,
+    );
+    report.unchecked = original.err();
+    report
+}
+
+/// The pristine version of a single-file exercise, or `None` for a crate
+/// exercise, which has no single pristine source.
+fn original_source(exercise: &Exercise) -> Result<Option<String>, String> {
+    if exercise.is_crate() {
+        return Ok(None);
+    }
+    pristine::original_source(&exercise.path)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+fn check(
+    exercise: &Exercise,
+    original: Option<&str>,
+    hidden_dir: Option<&Path>,
+    verbose: bool,
+) -> ExerciseReport {
+    let mut report = ExerciseReport {
+        name: exercise.name.clone(),
+        path: exercise.path.clone(),
+        section: exercise.section(),
+        mode: exercise.mode,
+        outcome: Outcome::Passed,
+        diagnostics: None,
+        failing_tests: vec![],
+        output_mismatches: vec![],
+        limit_exceeded: None,
+        hidden_tests: None,
+        protected_regions: vec![],
+        diff_budget: None,
+        unchecked: None,
+    };
+
+    let mut regions = vec![];
+    let mut budget = None;
+    if exercise.is_crate() {
+        regions = protect::check(exercise).unwrap_or_default();
+    } else if let (Some(original), Ok(current)) = (original, fs::read_to_string(&exercise.path)) {
+        regions = protect::violations(exercise, original, &current);
+        budget = budget::compare(original.to_string(), current);
+        if let Some(budget) = &budget {
+            report.diff_budget = Some(BudgetStatus {
+                allowed: budget.budget.to_string(),
+                used: budget.budget.describe(&budget.usage),
+                within: budget.within(),
+            });
+        }
+    }
+
+    report.protected_regions = regions
+        .iter()
+        .map(|region| EditedRegion {
+            location: region.location(),
+            reason: region.reason.clone(),
+        })
+        .collect();
+
+    report.outcome = match exercise.compile() {
+        Err(output) => {
+            if verbose {
+                warn!(
+                    "Compiling of {} failed! Please try again. Here's the output:",
+                    exercise
+                );
+                println!("{}", output.stderr);
+            }
+            report.diagnostics = Some(strip(&output.stderr));
+            Outcome::CompileError
+        }
+        Ok(compiled) => match exercise.mode {
+            Mode::Test => match compiled.run() {
+                Ok(_) => {
+                    if verbose {
+                        success!("Successfully tested {}!", exercise);
+                    }
+                    Outcome::Passed
+                }
+                Err(output) => {
+                    if verbose {
+                        match output.violation {
+                            Some(violation) => warn!(
+                                "{} {} and was stopped! Please try again. Here's the output:",
+                                exercise, violation
+                            ),
+                            None => warn!(
+                                "Testing of {} failed! Please try again. Here's the output:",
+                                exercise
+                            ),
+                        }
+                        println!("{}", output.stdout);
+                        println!("{}", output.stderr);
+                    }
+                    match output.violation {
+                        Some(violation) => {
+                            report.limit_exceeded = Some(format!("{} {}", exercise, violation));
+                            violation.into()
+                        }
+                        None => {
+                            report.failing_tests = failing_tests(&output.stdout);
+                            Outcome::TestFailure
+                        }
+                    }
+                }
+            },
+            Mode::Run | Mode::Qemu => {
+                let runs = expect::runs(exercise, &compiled);
+                if verbose && expect::check_runs(exercise, &runs, false) {
+                    success!("Successfully ran {}!", exercise);
+                }
+                let mut exceeded = None;
+                for run in runs {
+                    if let Some(violation) = run.output.violation {
+                        if exceeded.is_none() {
+                            report.limit_exceeded = Some(format!("{} {}", run.label, violation));
+                            exceeded = Some(violation);
+                        }
+                        continue;
+                    }
+                    for difference in expect::differences(&run.expected, &run.output) {
+                        report
+                            .output_mismatches
+                            .push(format!("{} {}", run.label, difference));
+                    }
+                }
+                match exceeded {
+                    Some(violation) => violation.into(),
+                    None if report.output_mismatches.is_empty() => Outcome::Passed,
+                    None => Outcome::WrongOutput,
+                }
+            }
+            Mode::Compile | Mode::Clippy | Mode::Xbuild => {
+                if verbose {
+                    success!("Successfully compiled {}!", exercise);
+                }
+                Outcome::Passed
+            }
+        },
+    };
+
+    if let Some(dir) = hidden_dir {
+        if report.outcome != Outcome::CompileError {
+            report.hidden_tests = hidden::run(exercise, dir);
+        }
+    }
+    if report.outcome == Outcome::Passed {
+        report.outcome = if matches!(&report.hidden_tests, Some(hidden) if !hidden.passed) {
+            Outcome::HiddenTestFailure
+        } else if !regions.is_empty() {
+            if verbose {
+                protect::print_violations(exercise, &regions);
+            }
+            Outcome::ProtectedRegion
+        } else if let Some(budget) = budget.filter(|b| !b.within()) {
+            if verbose {
+                budget::print_over(exercise, &budget);
+            }
+            Outcome::OverBudget
+        } else {
+            match exercise.state() {
+                Ok(State::Done) => Outcome::Passed,
+                _ => Outcome::Pending,
+            }
+        };
+    }
+    report
+}
+
+/// Formats reports as `format`, one of `FORMATS`.
+pub fn render(reports: &[ExerciseReport], format: &str) -> String {
+    match format {
+        "json" => serde_json::to_string_pretty(reports).unwrap(),
+        "junit" => junit(reports),
+        "tap" => tap(reports),
+        _ => unreachable!("unknown report format {}", format),
+    }
+}
+
+fn junit(reports: &[ExerciseReport]) -> String {
+    let count = |f: &dyn Fn(&ExerciseReport) -> bool| reports.iter().filter(|r| f(r)).count();
+    let skipped = |r: &ExerciseReport| r.outcome == Outcome::Pending;
+    let failed = |r: &ExerciseReport| r.outcome != Outcome::Passed && !skipped(r);
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"rustlings\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">",
+        reports.len(),
+        count(&failed),
+        count(&skipped)
+    );
+
+    let mut sections: Vec<&str> = vec![];
+    for report in reports {
+        if !sections.contains(&report.section.as_str()) {
+            sections.push(&report.section);
+        }
+    }
+    for section in sections {
+        let suite: Vec<&ExerciseReport> = reports.iter().filter(|r| r.section == section).collect();
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">",
+            escape(section),
+            suite.len(),
+            suite.iter().filter(|r| failed(r)).count(),
+            suite.iter().filter(|r| skipped(r)).count()
+        );
+        for report in suite {
+            let _ = write!(
+                out,
+                "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\"",
+                escape(&report.name),
+                escape(section),
+                escape(&report.path.display().to_string())
+            );
+            if report.outcome == Outcome::Passed {
+                out.push_str("/>\n");
+                continue;
+            }
+            out.push_str(">\n");
+            let element = if skipped(report) {
+                "skipped"
+            } else {
+                "failure"
+            };
+            let _ = writeln!(
+                out,
+                "      <{} type=\"{}\" message=\"{}\">{}</{}>",
+                element,
+                outcome_name(report.outcome),
+                escape(report.outcome.describe()),
+                escape(&details(report).join("\n")),
+                element
+            );
+            out.push_str("    </testcase>\n");
+        }
+        out.push_str("  </testsuite>\n");
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+fn tap(reports: &[ExerciseReport]) -> String {
+    let mut out = String::new();
+    out.push_str("TAP version 13\n");
+    let _ = writeln!(out, "1..{}", reports.len());
+    for (i, report) in reports.iter().enumerate() {
+        let status = if report.outcome == Outcome::Passed {
+            "ok"
+        } else {
+            "not ok"
+        };
+        let _ = write!(
+            out,
+            "{} {} - {} ({})",
+            status,
+            i + 1,
+            report.name,
+            report.path.display()
+        );
+        if report.outcome == Outcome::Pending {
+            let _ = write!(out, " # TODO {}", report.outcome.describe());
+        }
+        out.push('\n');
+        if report.outcome == Outcome::Passed {
+            continue;
+        }
+
+        out.push_str("  ---\n");
+        let _ = writeln!(out, "  mode: {}", report.mode);
+        let _ = writeln!(out, "  outcome: {}", outcome_name(report.outcome));
+        let details = details(report);
+        if !details.is_empty() {
+            out.push_str("  details: |\n");
+            for line in details.iter().flat_map(|d| d.lines()) {
+                let _ = writeln!(out, "    {}", line);
+            }
+        }
+        out.push_str("  ...\n");
+    }
+    out
+}
+
+/// The findings behind a report's outcome, as plain text lines.
+pub fn details(report: &ExerciseReport) -> Vec<String> {
+    let mut details = vec![];
+    details.extend(report.diagnostics.clone());
+    details.extend(
+        report
+            .failing_tests
+            .iter()
+            .map(|t| format!("test {} failed", t)),
+    );
+    details.extend(report.output_mismatches.iter().cloned());
+    details.extend(report.limit_exceeded.clone());
+    if let Some(hidden) = &report.hidden_tests {
+        details.extend(hidden.diagnostics.clone());
+        details.extend(
+            hidden
+                .failing_tests
+                .iter()
+                .map(|t| format!("hidden test {} failed", t)),
+        );
+    }
+    details.extend(
+        report
+            .protected_regions
+            .iter()
+            .map(|region| match &region.location {
+                Some(line) if line.parse::<usize>().is_ok() => {
+                    format!(
+                        "protected region edited at line {}: {}",
+                        line, region.reason
+                    )
+                }
+                Some(location) => {
+                    format!("protected region edited in {}: {}", location, region.reason)
+                }
+                None => format!("required line missing: {}", region.reason),
+            }),
+    );
+    details.extend(
+        report
+            .unchecked
+            .iter()
+            .map(|e| format!("protected regions and diff budget not checked: {}", e)),
+    );
+    if let Some(budget) = &report.diff_budget {
+        if !budget.within {
+            details.push(format!(
+                "diff budget: allowed {}, used {}",
+                budget.allowed, budget.used
+            ));
+        }
+    }
+    details
+}
+
+fn outcome_name(outcome: Outcome) -> String {
+    serde_json::to_value(outcome)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Returns the names of the tests a test binary reported as failed, from the
+/// `failures:` list it ends with. Unlike the `test ... FAILED` lines, that
+/// list cannot be split up by what the tests print under `--nocapture`.
+pub fn failing_tests(stdout: &str) -> Vec<String> {
+    let mut failing = vec![];
+    let mut in_list = false;
+    for line in stdout.lines() {
+        if line.trim_end() == "failures:" {
+            in_list = true;
+            continue;
+        }
+        match line.strip_prefix("    ") {
+            Some(name) if in_list && !name.trim().is_empty() => {
+                failing.push(name.trim().to_string())
+            }
+            _ => in_list = false,
+        }
+    }
+    failing
+}
+
+/// Removes colors from compiler output.
+pub fn strip(text: &str) -> String {
+    console::strip_ansi_codes(text).trim_end().to_string()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_failing_tests_in_captured_output() {
+        let stdout = "\nrunning 3 tests\ntest quiet ... ok\ntest loud ... FAILED\ntest tests::zfail ... FAILED\n\nfailures:\n\n---- loud stdout ----\nspam\n\n---- tests::zfail stdout ----\n\nfailures:\n    loud\n    tests::zfail\n\ntest result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out\n";
+        assert_eq!(failing_tests(stdout), vec!["loud", "tests::zfail"]);
+    }
+
+    #[test]
+    fn finds_failing_tests_that_print_under_nocapture() {
+        let stdout = "\nrunning 3 tests\ntest loud ... partial spam\n    indented spam\nFAILED\ntest quiet ... ok\ntest tests::zfail ... FAILED\n\nfailures:\n\nfailures:\n    loud\n    tests::zfail\n\ntest result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out\n";
+        assert_eq!(failing_tests(stdout), vec!["loud", "tests::zfail"]);
+    }
+
+    #[test]
+    fn finds_no_failing_tests_in_a_passing_run() {
+        let stdout = "\nrunning 1 test\ntest quiet ... ok\n\ntest result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out\n";
+        assert!(failing_tests(stdout).is_empty());
+    }
+}
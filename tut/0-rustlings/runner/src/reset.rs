@@ -0,0 +1,76 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::exercise::Exercise;
+use crate::package;
+use crate::pristine;
+use crate::progress;
+
+/// Where the student's versions are backed up before a reset, relative to
+/// `tut/0-rustlings`, as `<timestamp>/<exercise path>`.
+pub const BACKUP_DIR: &str = ".rustlings-backups";
+
+/// Restores each exercise to its pristine version, first backing up the
+/// student's version of any exercise that differs from it. Returns whether
+/// every exercise could be restored.
+pub fn reset(exercises: &[&Exercise]) -> bool {
+    let backup = Path::new(BACKUP_DIR).join(progress::file_timestamp());
+    let existed = backup.exists();
+    let mut failed = 0;
+    for exercise in exercises {
+        match reset_one(exercise, &backup) {
+            Ok(true) => success!("Reset {}!", exercise),
+            Ok(false) => progress!("{} is already as handed out.", exercise),
+            Err(e) => {
+                warn!("Could not reset {}: {}", exercise, e);
+                failed += 1;
+            }
+        }
+    }
+    if !existed && backup.exists() {
+        println!("Your previous versions are saved in {}.", backup.display());
+    }
+    failed == 0
+}
+
+/// Restores a single exercise, returning whether anything changed.
+fn reset_one(exercise: &Exercise, backup: &Path) -> io::Result<bool> {
+    // A crate exercise the student deleted is no longer a directory, but
+    // still has no `.rs` extension.
+    if exercise.is_crate() || exercise.path.extension().is_none() {
+        return reset_crate(exercise, backup);
+    }
+
+    let original = pristine::original_source(&exercise.path)?;
+    match fs::read(&exercise.path) {
+        Ok(current) if current == original.as_bytes() => return Ok(false),
+        Ok(_) => {
+            let saved = backup.join(&exercise.path);
+            fs::create_dir_all(saved.parent().unwrap_or(backup))?;
+            fs::copy(&exercise.path, saved)?;
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    fs::write(&exercise.path, original)?;
+    Ok(true)
+}
+
+fn reset_crate(exercise: &Exercise, backup: &Path) -> io::Result<bool> {
+    let scratch = exercise.temp_binary();
+    let _ = fs::remove_dir_all(&scratch);
+    let result = pristine::original_tree(&exercise.path, &scratch).and_then(|()| {
+        if exercise.path.exists() {
+            if package::read_tree(&exercise.path)? == package::read_tree(&scratch)? {
+                return Ok(false);
+            }
+            package::copy_dir(&exercise.path, &backup.join(&exercise.path))?;
+            fs::remove_dir_all(&exercise.path)?;
+        }
+        package::copy_dir(&scratch, &exercise.path)?;
+        Ok(true)
+    });
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
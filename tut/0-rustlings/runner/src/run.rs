@@ -0,0 +1,67 @@
+use crate::budget;
+use crate::exercise::{Exercise, Mode};
+use crate::expect;
+use crate::protect;
+
+/// Compiles a single exercise and, depending on its mode, tests it or runs it
+/// and checks its output, regardless of its `I AM NOT DONE` marker.
+pub fn run(exercise: &Exercise) -> Result<(), ()> {
+    progress!("Compiling {}...", exercise);
+    let compiled = match exercise.compile() {
+        Ok(compiled) => compiled,
+        Err(output) => {
+            warn!(
+                "Compilation of {} failed! Compiler error message:\n",
+                exercise
+            );
+            println!("{}", output.stderr);
+            return Err(());
+        }
+    };
+
+    if let Mode::Compile | Mode::Clippy | Mode::Xbuild = exercise.mode {
+        success!("Successfully compiled {}!", exercise);
+        return check_changes(exercise);
+    }
+
+    if let Mode::Run | Mode::Qemu = exercise.mode {
+        progress!("Running {}...", exercise);
+        if !expect::run_and_check(exercise, &compiled, true) {
+            return Err(());
+        }
+        success!("Successfully ran {}!", exercise);
+        return check_changes(exercise);
+    }
+
+    progress!("Testing {}...", exercise);
+    match compiled.run() {
+        Ok(output) => {
+            println!("{}", output.stdout);
+            success!("Successfully tested {}!", exercise);
+            check_changes(exercise)
+        }
+        Err(output) => {
+            match output.violation {
+                Some(violation) => warn!(
+                    "{} {} and was stopped! Please try again. Here's the output:",
+                    exercise, violation
+                ),
+                None => warn!(
+                    "Testing of {} failed! Please try again. Here's the output:",
+                    exercise
+                ),
+            }
+            println!("{}", output.stdout);
+            println!("{}", output.stderr);
+            Err(())
+        }
+    }
+}
+
+fn check_changes(exercise: &Exercise) -> Result<(), ()> {
+    if protect::untouched(exercise) && budget::within_budget(exercise) {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
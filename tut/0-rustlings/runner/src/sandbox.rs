@@ -0,0 +1,334 @@
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::exercise::Limits;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// How long to keep reading output after the process has exited and its
+/// process group has been killed. Only a process that left the group can
+/// still hold the pipes by then.
+const DRAIN_GRACE: Duration = Duration::from_secs(1);
+
+/// The signal a process gets once it has used up its CPU time.
+const SIGXCPU: i32 = 24;
+
+/// What the Rust runtime prints when an allocation fails.
+const ALLOCATION_FAILURE: &str = "memory allocation of ";
+
+/// A limit a process was stopped for exceeding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// Seconds of wall-clock time.
+    Time(u64),
+    /// Seconds of CPU time.
+    Cpu(u64),
+    /// Mebibytes of memory.
+    Memory(u64),
+    /// Bytes of stdout or stderr.
+    Output(usize),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::Time(seconds) => write!(f, "ran for more than {}s", seconds),
+            Violation::Cpu(seconds) => write!(f, "used more than {}s of CPU time", seconds),
+            Violation::Memory(mib) => write!(f, "used more than {} MiB of memory", mib),
+            Violation::Output(bytes) => write!(f, "printed more than {} bytes", bytes),
+        }
+    }
+}
+
+/// Runs `cmd` to completion like `Command::output`, with `input` fed to its
+/// stdin if given, under `limits`. It is killed, along with any process it
+/// started, once it runs out of time or prints too much, and whatever it
+/// leaves behind is killed once it exits; the CPU and memory limits are
+/// enforced by the kernel through `ulimit`. Returns its output,
+/// cut off at `limits.output` bytes per stream, and the limit it exceeded.
+pub fn output(
+    cmd: &Command,
+    limits: &Limits,
+    input: Option<&[u8]>,
+) -> io::Result<(Output, Option<Violation>)> {
+    let stdin = if input.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    };
+    let mut child = confine(cmd, limits)
+        .stdin(stdin)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Feed stdin from another thread so a chatty binary cannot deadlock
+    // against us on a full stdout pipe. The binary may exit without reading
+    // all of it, so write errors are ignored, and the thread is not waited
+    // for.
+    if let Some(mut stdin) = child.stdin.take() {
+        let input = input.unwrap_or_default().to_vec();
+        thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+    }
+    let overflow = Arc::new(AtomicBool::new(false));
+    let stdout = drain(child.stdout.take().unwrap(), limits.output, &overflow);
+    let stderr = drain(child.stderr.take().unwrap(), limits.output, &overflow);
+
+    let start = Instant::now();
+    let mut violation = None;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if overflow.load(Ordering::Relaxed) {
+            violation = Some(Violation::Output(limits.output));
+        } else if start.elapsed() >= Duration::from_secs(limits.time) {
+            violation = Some(Violation::Time(limits.time));
+        }
+        if violation.is_some() {
+            kill(&mut child);
+            break child.wait()?;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    // Whatever the process started in the background may still hold the
+    // pipes open, and reading them would wait for it.
+    kill_group(child.id());
+    let deadline = Instant::now() + DRAIN_GRACE;
+    let output = Output {
+        status,
+        stdout: stdout.collect(deadline),
+        stderr: stderr.collect(deadline),
+    };
+    let violation = violation
+        .or_else(|| {
+            overflow
+                .load(Ordering::Relaxed)
+                .then_some(Violation::Output(limits.output))
+        })
+        .or_else(|| exhausted(&output, limits));
+    Ok((output, violation))
+}
+
+/// Wraps `cmd` in a shell that sets the CPU and memory limits before it
+/// `exec`s the command, in a process group of its own so that killing it
+/// also kills what it started, such as the test binaries of `cargo test`.
+fn confine(cmd: &Command, limits: &Limits) -> Command {
+    let mut script = String::new();
+    if let Some(cpu) = limits.cpu {
+        // At the soft limit the kernel sends `SIGXCPU`, which tells it apart
+        // from being killed; the hard limit only backs it up.
+        script.push_str(&format!(
+            "ulimit -S -t {} && ulimit -H -t {} && ",
+            cpu,
+            cpu + 1
+        ));
+    }
+    if let Some(mib) = limits.memory {
+        script.push_str(&format!("ulimit -d {} && ", mib * 1024));
+    }
+    script.push_str("exec \"$0\" \"$@\"");
+
+    let mut confined = Command::new("sh");
+    confined
+        .arg("-c")
+        .arg(script)
+        .arg(cmd.get_program())
+        .args(cmd.get_args())
+        .process_group(0);
+    for (key, value) in cmd.get_envs() {
+        match value {
+            Some(value) => confined.env(key, value),
+            None => confined.env_remove(key),
+        };
+    }
+    if let Some(dir) = cmd.get_current_dir() {
+        confined.current_dir(dir);
+    }
+    confined
+}
+
+fn kill(child: &mut Child) {
+    kill_group(child.id());
+    let _ = child.kill();
+}
+
+/// Kills every process left in the process group `confine` put the process
+/// with `pid` in.
+fn kill_group(pid: u32) {
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", pid)])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+/// Returns the limit a failed process ran into on its own: the CPU limit if
+/// it, or a test binary `cargo` ran, got `SIGXCPU`, the memory limit if an
+/// allocation failed.
+fn exhausted(output: &Output, limits: &Limits) -> Option<Violation> {
+    if output.status.success() {
+        return None;
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if let Some(cpu) = limits.cpu {
+        if output.status.signal() == Some(SIGXCPU) || stderr.contains("SIGXCPU") {
+            return Some(Violation::Cpu(cpu));
+        }
+    }
+    if let Some(mib) = limits.memory {
+        if stderr.contains(ALLOCATION_FAILURE) {
+            return Some(Violation::Memory(mib));
+        }
+    }
+    None
+}
+
+/// The output read so far from a pipe being drained on another thread.
+struct Drain {
+    kept: Arc<Mutex<Vec<u8>>>,
+    reader: thread::JoinHandle<()>,
+}
+
+impl Drain {
+    /// Waits for the pipe to close, but no later than `deadline`, and
+    /// returns what was read from it.
+    fn collect(self, deadline: Instant) -> Vec<u8> {
+        while !self.reader.is_finished() && Instant::now() < deadline {
+            thread::sleep(POLL_INTERVAL);
+        }
+        let mut kept = self.kept.lock().unwrap_or_else(|e| e.into_inner());
+        std::mem::take(&mut *kept)
+    }
+}
+
+/// Reads `pipe` to its end on another thread, keeping at most `limit` bytes
+/// and raising `overflow` if there were more.
+fn drain<R: Read + Send + 'static>(mut pipe: R, limit: usize, overflow: &Arc<AtomicBool>) -> Drain {
+    let overflow = Arc::clone(overflow);
+    let kept = Arc::new(Mutex::new(vec![]));
+    let shared = Arc::clone(&kept);
+    let reader = thread::spawn(move || {
+        let mut buf = [0; 8192];
+        loop {
+            let n = match pipe.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => break,
+            };
+            let mut kept = shared.lock().unwrap_or_else(|e| e.into_inner());
+            let room = limit.saturating_sub(kept.len());
+            if n > room {
+                overflow.store(true, Ordering::Relaxed);
+            }
+            kept.extend_from_slice(&buf[..n.min(room)]);
+        }
+    });
+    Drain { kept, reader }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(script: &str, limits: Limits) -> (Output, Option<Violation>) {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script);
+        output(&cmd, &limits, None).unwrap()
+    }
+
+    #[test]
+    fn passes_well_behaved_processes() {
+        let (output, violation) = run("echo hi", Limits::default());
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hi\n");
+        assert_eq!(violation, None);
+    }
+
+    #[test]
+    fn feeds_stdin() {
+        let cmd = Command::new("cat");
+        let (output, _) = output(&cmd, &Limits::default(), Some(b"fixture")).unwrap();
+        assert_eq!(output.stdout, b"fixture");
+    }
+
+    #[test]
+    fn stops_processes_that_run_out_of_time() {
+        let limits = Limits {
+            time: 1,
+            ..Limits::default()
+        };
+        let start = Instant::now();
+        let (output, violation) = run("echo started; sleep 30", limits);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(violation, Some(Violation::Time(1)));
+        assert_eq!(output.stdout, b"started\n");
+    }
+
+    #[test]
+    fn stops_processes_that_run_out_of_cpu_time() {
+        let limits = Limits {
+            cpu: Some(1),
+            ..Limits::default()
+        };
+        let (_, violation) = run("while :; do :; done", limits);
+        assert_eq!(violation, Some(Violation::Cpu(1)));
+    }
+
+    #[test]
+    fn stops_processes_that_print_too_much() {
+        let limits = Limits {
+            output: 1000,
+            ..Limits::default()
+        };
+        let (output, violation) = run("yes", limits);
+        assert_eq!(violation, Some(Violation::Output(1000)));
+        assert_eq!(output.stdout.len(), 1000);
+    }
+
+    #[test]
+    fn sets_the_cpu_and_memory_limits() {
+        let limits = Limits {
+            cpu: Some(3),
+            memory: Some(16),
+            ..Limits::default()
+        };
+        let (output, _) = run("ulimit -t; ulimit -d", limits);
+        assert_eq!(output.stdout, b"3\n16384\n");
+    }
+
+    #[test]
+    fn recognizes_failed_allocations() {
+        let output = Output {
+            status: ExitStatusExt::from_raw(6),
+            stdout: vec![],
+            stderr: b"memory allocation of 1048576 bytes failed\n".to_vec(),
+        };
+        let limits = Limits::default();
+        assert_eq!(exhausted(&output, &limits), Some(Violation::Memory(1024)));
+        let unlimited = Limits {
+            memory: None,
+            ..limits
+        };
+        assert_eq!(exhausted(&output, &unlimited), None);
+    }
+
+    #[test]
+    fn does_not_wait_for_background_processes() {
+        let start = Instant::now();
+        let (output, violation) = run("sleep 30 & echo hi", Limits::default());
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(output.stdout, b"hi\n");
+        assert_eq!(violation, None);
+    }
+}
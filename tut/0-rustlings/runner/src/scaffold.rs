@@ -0,0 +1,302 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use toml::Value;
+
+use crate::budget::Budget;
+use crate::exercise::{ExerciseList, Mode};
+use crate::selftest;
+
+/// The modes `rustlings new` has a template for.
+pub const MODES: &[&str] = &["compile", "test", "run", "clippy"];
+
+const EXERCISES_DIR: &str = "exercises";
+
+/// An exercise for `rustlings new` to create.
+pub struct NewExercise<'a> {
+    pub section: &'a str,
+    pub name: &'a str,
+    pub mode: Mode,
+    /// The diff budget for the `// FIXME` header, e.g. `3 lines`.
+    pub budget: Option<&'a str>,
+    /// The exercise the new entry goes after. Defaults to the last exercise
+    /// of the section, or the end of `info.toml` for a new section.
+    pub after: Option<&'a str>,
+    pub hint: Option<&'a str>,
+    /// Whether to create a reference solution to fill in as well.
+    pub solution: bool,
+}
+
+/// Creates the exercise file from a template for its mode, with the
+/// `// FIXME` header and the `// I AM NOT DONE` marker, adds its entry to
+/// `info` and, if asked to, creates a solution stub that does not compile
+/// until it is written.
+pub fn scaffold(list: &ExerciseList, new: &NewExercise, info: &Path) -> io::Result<()> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+    for part in &[new.section, new.name] {
+        if part.is_empty()
+            || !part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(invalid(format!(
+                "`{}` may only have letters, digits, `-` and `_`",
+                part
+            )));
+        }
+    }
+    if list.find(new.name).is_some() {
+        return Err(invalid(format!(
+            "there already is an exercise named `{}`",
+            new.name
+        )));
+    }
+    let path: PathBuf = [EXERCISES_DIR, new.section, &format!("{}.rs", new.name)]
+        .iter()
+        .collect();
+    if path.exists() {
+        return Err(invalid(format!("{} already exists", path.display())));
+    }
+    let mut header = match new.mode {
+        Mode::Test | Mode::Run => "// FIXME: Make me pass!".to_string(),
+        _ => "// FIXME: Make me compile!".to_string(),
+    };
+    if let Some(budget) = new.budget {
+        header.push_str(&format!(" Diff budget: {}.", budget));
+        if Budget::parse(&header).is_none() {
+            return Err(invalid(format!(
+                "`{}` is not a diff budget; try e.g. `3 lines` or `2 line additions and 5 characters`",
+                budget
+            )));
+        }
+    }
+
+    let text = fs::read_to_string(info)?;
+    let (at, after) = placement(list, &text, new).map_err(invalid)?;
+
+    let body = template(new.mode);
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, format!("{}\n\n// I AM NOT DONE\n\n{}", header, body))?;
+    success!("Created {}.", path.display());
+
+    let mut entry = String::new();
+    if after.is_none() {
+        entry.push_str(&format!("\n# {}\n", new.section.to_uppercase()));
+    }
+    let hint = new.hint.map_or_else(
+        || format!("FIXME: write a hint for {}.", new.name),
+        str::to_string,
+    );
+    entry.push_str(&format!(
+        "\n[[exercises]]\nname = {}\npath = {}\nmode = {}\n",
+        Value::String(new.name.to_string()),
+        Value::String(path.to_string_lossy().replace('\\', "/")),
+        Value::String(new.mode.to_string()),
+    ));
+    if new.mode == Mode::Run {
+        entry.push_str(&format!(
+            "expected_output = {}\n",
+            Value::String(format!("FIXME: what the solution of {} prints.", new.name))
+        ));
+    }
+    entry.push_str(&format!("hint = {}\n", Value::String(hint)));
+    fs::write(info, insert(text, at, &entry))?;
+    match &after {
+        Some(after) => success!("Added {} to {} after {}.", new.name, info.display(), after),
+        None => success!("Added {} to the end of {}.", new.name, info.display()),
+    }
+
+    if new.solution {
+        let solution = Path::new(selftest::SOLUTIONS_DIR).join(format!("{}.rs", new.name));
+        fs::create_dir_all(solution.parent().unwrap())?;
+        fs::write(
+            &solution,
+            format!(
+                "{}\n\ncompile_error!(\"FIXME: write the solution of {}\");\n",
+                header, new.name
+            ),
+        )?;
+        success!("Created {}.", solution.display());
+    }
+    Ok(())
+}
+
+/// Returns where in `text`, the contents of `info.toml`, the entry of `new`
+/// goes, and the exercise it goes after, if any.
+fn placement(
+    list: &ExerciseList,
+    text: &str,
+    new: &NewExercise,
+) -> Result<(usize, Option<String>), String> {
+    match new.after {
+        Some(after) => match end_of_entry(text, after) {
+            Some(at) => Ok((at, Some(after.to_string()))),
+            None => Err(format!("there is no exercise named `{}`", after)),
+        },
+        None => {
+            let last = list
+                .exercises
+                .iter()
+                .rev()
+                .find(|e| e.section() == new.section);
+            match last.and_then(|e| end_of_entry(text, &e.name).map(|at| (at, &e.name))) {
+                Some((at, name)) => Ok((at, Some(name.clone()))),
+                None => Ok((text.len(), None)),
+            }
+        }
+    }
+}
+
+/// Inserts `entry` into `text` at `at`, on a line of its own.
+fn insert(mut text: String, at: usize, entry: &str) -> String {
+    if at == text.len() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    let at = at.min(text.len());
+    text.insert_str(at, entry);
+    text
+}
+
+/// The code a new exercise of `mode` starts out with. Each one fails its
+/// mode until it is edited: a `run` exercise by printing something other
+/// than the placeholder `expected_output` its entry gets.
+fn template(mode: Mode) -> &'static str {
+    match mode {
+        Mode::Test => {
+            "#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn works() {\n        assert!(false);\n    }\n}\n"
+        }
+        Mode::Run => "fn main() {\n    println!(\"Hello, world!\");\n}\n",
+        Mode::Clippy => "fn main() {\n    println!(\"{}\", answer());\n}\n\nfn answer() -> u32 {\n    return 42;\n}\n",
+        _ => "fn main() {\n    compile_error!(\"FIXME: write code that does not compile yet\");\n}\n",
+    }
+}
+
+/// Returns where the `info.toml` entry of `name` ends in `text`: before the
+/// comments and blank lines that lead up to the next entry, or at the end.
+fn end_of_entry(text: &str, name: &str) -> Option<usize> {
+    let line = format!("name = {}", Value::String(name.to_string()));
+    let mut offset = 0;
+    let mut start = None;
+    for l in text.split_inclusive('\n') {
+        if l.trim() == line {
+            start = Some(offset);
+            break;
+        }
+        offset += l.len();
+    }
+    let start = start?;
+    let mut end = match text[start..].find("\n[[exercises]]") {
+        Some(i) => start + i + 1,
+        None => return Some(text.len()),
+    };
+    while end > start {
+        let previous = text[..end - 1].rfind('\n').map_or(0, |i| i + 1);
+        let l = text[previous..end].trim();
+        if !l.is_empty() && !l.starts_with('#') {
+            break;
+        }
+        end = previous;
+    }
+    Some(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INFO: &str = "# VARIABLES
+
+[[exercises]]
+name = \"variables1\"
+path = \"exercises/variables/variables1.rs\"
+mode = \"compile\"
+hint = \"\"
+
+[[exercises]]
+name = \"variables2\"
+path = \"exercises/variables/variables2.rs\"
+mode = \"compile\"
+hint = \"\"
+
+[[exercises.explanations]]
+code = \"E0282\"
+text = \"\"
+
+# IF
+
+[[exercises]]
+name = \"if1\"
+path = \"exercises/if/if1.rs\"
+mode = \"test\"
+hint = \"\"";
+
+    fn new<'a>(section: &'a str, after: Option<&'a str>) -> NewExercise<'a> {
+        NewExercise {
+            section,
+            name: "new1",
+            mode: Mode::Test,
+            budget: None,
+            after,
+            hint: None,
+            solution: false,
+        }
+    }
+
+    /// Adds a minimal entry for `new` the way `scaffold` places it, and
+    /// returns the names in `info.toml` in order.
+    fn names_after_adding(new: &NewExercise) -> Result<Vec<String>, String> {
+        let list: ExerciseList = toml::from_str(INFO).unwrap();
+        let (at, _) = placement(&list, INFO, new)?;
+        let entry = format!(
+            "\n[[exercises]]\nname = \"{0}\"\npath = \"exercises/{1}/{0}.rs\"\nmode = \"test\"\nhint = \"\"\n",
+            new.name, new.section
+        );
+        let text = insert(INFO.to_string(), at, &entry);
+        let list: ExerciseList = toml::from_str(&text).map_err(|e| e.to_string())?;
+        Ok(list.exercises.into_iter().map(|e| e.name).collect())
+    }
+
+    #[test]
+    fn adds_exercises_at_the_end_of_their_section() {
+        assert_eq!(
+            names_after_adding(&new("variables", None)).unwrap(),
+            ["variables1", "variables2", "new1", "if1"]
+        );
+    }
+
+    #[test]
+    fn adds_exercises_after_the_one_asked_for() {
+        assert_eq!(
+            names_after_adding(&new("variables", Some("variables1"))).unwrap(),
+            ["variables1", "new1", "variables2", "if1"]
+        );
+        assert_eq!(
+            names_after_adding(&new("if", Some("if1"))).unwrap(),
+            ["variables1", "variables2", "if1", "new1"]
+        );
+        assert!(names_after_adding(&new("if", Some("if9"))).is_err());
+    }
+
+    #[test]
+    fn adds_new_sections_at_the_end() {
+        let list: ExerciseList = toml::from_str(INFO).unwrap();
+        assert_eq!(
+            placement(&list, INFO, &new("loops", None)),
+            Ok((INFO.len(), None))
+        );
+        assert_eq!(
+            names_after_adding(&new("loops", None)).unwrap(),
+            ["variables1", "variables2", "if1", "new1"]
+        );
+    }
+
+    #[test]
+    fn keeps_explanations_and_section_comments_with_their_entries() {
+        let at = end_of_entry(INFO, "variables2").unwrap();
+        assert!(INFO[..at].ends_with("text = \"\"\n"));
+        assert!(INFO[at..].starts_with("\n# IF\n"));
+        assert_eq!(end_of_entry(INFO, "if1"), Some(INFO.len()));
+        assert_eq!(end_of_entry(INFO, "if"), None);
+    }
+}
@@ -0,0 +1,139 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::exercise::{Exercise, ExerciseList};
+use crate::hidden;
+use crate::package;
+use crate::pristine;
+use crate::protect;
+use crate::report::{self, Outcome};
+
+/// Where reference solutions live, relative to `tut/0-rustlings`, as
+/// `<name>.rs` for the `info.toml` name of each exercise, or as a `<name>`
+/// directory for crate exercises.
+pub const SOLUTIONS_DIR: &str = "solutions";
+
+/// Returns where the reference solution of `exercise` is kept.
+pub fn solution_path(exercise: &Exercise) -> PathBuf {
+    if exercise.is_crate() {
+        Path::new(SOLUTIONS_DIR).join(&exercise.name)
+    } else {
+        Path::new(SOLUTIONS_DIR).join(format!("{}.rs", exercise.name))
+    }
+}
+
+/// Checks that every starter fails in its mode, and that every reference
+/// solution passes, leaves protected regions alone and stays within the
+/// starter's diff budget. Returns whether all exercises hold up.
+pub fn selftest(list: &ExerciseList) -> bool {
+    let scratch = env::temp_dir().join(format!("rustlings_{}_selftest", process::id()));
+    let hidden_dir = hidden::dir(None);
+    let mut passed = 0;
+    for exercise in &list.exercises {
+        progress!("Self-testing {}...", exercise);
+        let problems = match check(exercise, &scratch, hidden_dir.as_deref()) {
+            Ok(problems) => problems,
+            Err(e) => vec![format!("could not read the starter: {}", e)],
+        };
+        if problems.is_empty() {
+            passed += 1;
+            continue;
+        }
+        for problem in problems {
+            warn!("{}: {}", exercise.name, problem);
+        }
+    }
+    let _ = fs::remove_dir_all(&scratch);
+
+    for orphan in orphaned_solutions(list) {
+        warn!(
+            "{} does not belong to any exercise in info.toml",
+            orphan.display()
+        );
+    }
+
+    let total = list.exercises.len();
+    if passed == total {
+        success!("All {} exercises passed their self-test!", total);
+    } else {
+        warn!("{} of {} exercises passed their self-test.", passed, total);
+    }
+    passed == total
+}
+
+fn check(
+    exercise: &Exercise,
+    scratch: &Path,
+    hidden_dir: Option<&Path>,
+) -> io::Result<Vec<String>> {
+    let mut problems = vec![];
+
+    // Check the starter as handed out, not whatever is in the working tree.
+    // Crate exercises have no diff budget, and their solutions are compared
+    // against the starter's files for protected regions further down.
+    fs::create_dir_all(scratch)?;
+    let mut starter = exercise.clone();
+    starter.path = scratch.join(exercise.path.file_name().unwrap_or_default());
+    let original = if exercise.is_crate() {
+        let _ = fs::remove_dir_all(&starter.path);
+        if pristine::original_tree(&exercise.path, &starter.path).is_err() {
+            package::copy_dir(&exercise.path, &starter.path)?;
+        }
+        None
+    } else {
+        let original = match pristine::original_source(&exercise.path) {
+            Ok(original) => original,
+            Err(_) => fs::read_to_string(&exercise.path)?,
+        };
+        fs::write(&starter.path, &original)?;
+        Some(original)
+    };
+    let outcome = report::evaluate_against(&starter, None, None).outcome;
+    if let Outcome::Passed | Outcome::Pending = outcome {
+        problems.push(format!(
+            "the starter already passes as a `{}` exercise",
+            exercise.mode
+        ));
+    }
+
+    let mut solution = exercise.clone();
+    solution.path = solution_path(exercise);
+    if !solution.path.exists() {
+        problems.push(format!("no solution at {}", solution.path.display()));
+        return Ok(problems);
+    }
+    let report = report::evaluate_against(&solution, original.as_deref(), hidden_dir);
+    if report.outcome != Outcome::Passed {
+        problems.push(format!("the solution {}", report.outcome.describe()));
+        problems.extend(report::details(&report));
+    }
+    if exercise.is_crate() {
+        for region in protect::crate_violations(&starter.path, &solution.path)? {
+            problems.push(format!(
+                "the solution edits {} of the starter: {}",
+                region.location().unwrap_or_default(),
+                region.reason
+            ));
+        }
+    }
+    Ok(problems)
+}
+
+fn orphaned_solutions(list: &ExerciseList) -> Vec<PathBuf> {
+    let entries = match fs::read_dir(SOLUTIONS_DIR) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            !list
+                .exercises
+                .iter()
+                .any(|exercise| solution_path(exercise) == *path)
+        })
+        .collect()
+}
@@ -0,0 +1,115 @@
+use std::io;
+
+use console::style;
+
+use crate::exercise::{Exercise, ExerciseList};
+use crate::progress::{self, Progress, Record};
+use crate::topics;
+
+/// Prints a per-section summary of recorded progress in `info.toml` order,
+/// or a per-exercise table if `section` is given.
+pub fn status(list: &ExerciseList, section: Option<&str>) -> io::Result<()> {
+    let progress = Progress::load()?;
+    match section {
+        Some(section) => print_section(list, &progress, section),
+        None => print_summary(list, &progress),
+    }
+    Ok(())
+}
+
+fn print_summary(list: &ExerciseList, progress: &Progress) {
+    println!(
+        "{}",
+        style(format!(
+            "{:<16} {:>7} {:>8} {:>9} {:>6}  {}",
+            "Section", "Done", "Started", "Attempts", "Hints", "Stuck on"
+        ))
+        .bold()
+    );
+    for (section, exercises) in list.sections() {
+        let records: Vec<Record> = exercises
+            .iter()
+            .map(|e| progress.get(&e.name).cloned().unwrap_or_default())
+            .collect();
+        let done = records.iter().filter(|r| r.passed.is_some()).count();
+        let started = records.iter().filter(|r| r.started.is_some()).count();
+        let attempts: u32 = records.iter().map(|r| r.attempts).sum();
+        let hints: u32 = records.iter().map(|r| r.hints).sum();
+        let stuck = exercises
+            .iter()
+            .zip(&records)
+            .find(|(_, r)| r.started.is_some() && r.passed.is_none())
+            .map(|(e, r)| format!("{} ({} attempts)", e.name, r.attempts))
+            .unwrap_or_default();
+
+        let line = format!(
+            "{:<16} {:>7} {:>8} {:>9} {:>6}  {}",
+            section,
+            format!("{}/{}", done, exercises.len()),
+            started,
+            attempts,
+            hints,
+            stuck
+        );
+        if done == exercises.len() {
+            println!("{}", style(line).green());
+        } else {
+            println!("{}", line);
+        }
+    }
+
+    let total = list.exercises.len();
+    let done = list
+        .exercises
+        .iter()
+        .filter(|e| progress.get(&e.name).and_then(|r| r.passed).is_some())
+        .count();
+    println!();
+    println!("{} of {} exercises done.", done, total);
+    let unlocked: Vec<&Exercise> = list
+        .ordered()
+        .into_iter()
+        .filter(|e| progress.get(&e.name).and_then(|r| r.passed).is_none())
+        .filter(|e| topics::unfinished_requirements(list, progress, e).is_empty())
+        .collect();
+    if let Some(next) = unlocked.first() {
+        println!("Next up: {} ({})", next.name, next);
+    }
+    if unlocked.len() > 1 {
+        let others: Vec<&str> = unlocked[1..].iter().map(|e| e.name.as_str()).collect();
+        println!("Also unlocked: {}", others.join(", "));
+    }
+}
+
+fn print_section(list: &ExerciseList, progress: &Progress, section: &str) {
+    let exercises: Vec<&Exercise> = list
+        .exercises
+        .iter()
+        .filter(|e| e.section() == section)
+        .collect();
+    if exercises.is_empty() {
+        warn!("No section named `{}`.", section);
+        return;
+    }
+
+    println!(
+        "{}",
+        style(format!(
+            "{:<20} {:<16} {:<16} {:>9} {:>6}",
+            "Exercise", "Started", "Passed", "Attempts", "Hints"
+        ))
+        .bold()
+    );
+    for exercise in exercises {
+        let record = progress.get(&exercise.name).cloned().unwrap_or_default();
+        let time = |t: Option<u64>| t.map(progress::format_time).unwrap_or_else(|| "-".into());
+        println!(
+            "{:<20} {:<16} {:<16} {:>9} {:>6}",
+            exercise.name,
+            time(record.started),
+            time(record.passed),
+            record.attempts,
+            record.hints
+        );
+    }
+}
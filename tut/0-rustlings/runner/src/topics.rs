@@ -0,0 +1,225 @@
+use std::io;
+
+use console::style;
+
+use crate::exercise::{Exercise, ExerciseList};
+use crate::progress::Progress;
+
+/// Prints every topic with its exercises and what each requires, or, if
+/// `topic` is given, every exercise on its path in the order to do them.
+pub fn topics(list: &ExerciseList, topic: Option<&str>) -> io::Result<()> {
+    let progress = Progress::load()?;
+    match topic {
+        Some(topic) => print_path(list, &progress, topic),
+        None => print_map(list, &progress),
+    }
+    Ok(())
+}
+
+fn print_map(list: &ExerciseList, progress: &Progress) {
+    for (topic, exercises) in list.topics() {
+        let done = exercises.iter().filter(|e| passed(progress, e)).count();
+        println!(
+            "{} {}",
+            style(&topic).bold(),
+            style(format!(
+                "({}/{} done, {} with prerequisites)",
+                done,
+                exercises.len(),
+                list.path(&topic).len()
+            ))
+            .dim()
+        );
+        for exercise in exercises {
+            print_exercise(list, progress, exercise);
+        }
+        println!();
+    }
+    println!("Run `rustlings topics <topic>` to see everything a topic builds on.");
+}
+
+fn print_path(list: &ExerciseList, progress: &Progress, topic: &str) {
+    let path = list.path(topic);
+    if path.is_empty() {
+        warn!("No topic named `{}`.", topic);
+        return;
+    }
+    println!("{}", style(format!("The {} path, in order:", topic)).bold());
+    for exercise in path {
+        print_exercise(list, progress, exercise);
+    }
+    println!();
+    println!(
+        "Run `rustlings watch --topic {}` to work through it.",
+        topic
+    );
+}
+
+fn print_exercise(list: &ExerciseList, progress: &Progress, exercise: &Exercise) {
+    let requires: Vec<&str> = list
+        .requirements(exercise)
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect();
+    let requires = if requires.is_empty() {
+        String::new()
+    } else {
+        format!("requires {}", requires.join(", "))
+    };
+    let line = |state: &str| {
+        let line = format!("  {:<7} {:<20} {}", state, exercise.name, requires);
+        line.trim_end().to_string()
+    };
+    if passed(progress, exercise) {
+        println!("{}", style(line("done")).green());
+    } else if unfinished_requirements(list, progress, exercise).is_empty() {
+        println!("{}", line("open"));
+    } else {
+        println!("{}", style(line("locked")).dim());
+    }
+}
+
+/// Returns the exercises `exercise` requires that have not passed yet.
+pub fn unfinished_requirements<'a>(
+    list: &'a ExerciseList,
+    progress: &Progress,
+    exercise: &Exercise,
+) -> Vec<&'a Exercise> {
+    list.requirements(exercise)
+        .into_iter()
+        .filter(|e| !passed(progress, e))
+        .collect()
+}
+
+/// Warns if `exercise` is still locked, without stopping anyone from trying
+/// it anyway.
+pub fn warn_if_locked(list: &ExerciseList, exercise: &Exercise) {
+    let progress = match Progress::load() {
+        Ok(progress) => progress,
+        Err(_) => return,
+    };
+    let unfinished = unfinished_requirements(list, &progress, exercise);
+    if unfinished.is_empty() {
+        return;
+    }
+    let names: Vec<&str> = unfinished.iter().map(|e| e.name.as_str()).collect();
+    let verb = if names.len() == 1 { "is" } else { "are" };
+    warn!(
+        "{} builds on {}, which {} not done yet.",
+        exercise.name,
+        names.join(", "),
+        verb
+    );
+}
+
+fn passed(progress: &Progress, exercise: &Exercise) -> bool {
+    progress
+        .get(&exercise.name)
+        .and_then(|r| r.passed)
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::progress::Record;
+
+    /// An entry's name, topics and requirements, where `None` requires the
+    /// entry before.
+    type Entry<'a> = (&'a str, &'a [&'a str], Option<&'a [&'a str]>);
+
+    fn list(entries: &[Entry]) -> ExerciseList {
+        let mut text = String::new();
+        for (name, topics, requires) in entries {
+            text.push_str(&format!(
+                "[[exercises]]\nname = \"{0}\"\npath = \"exercises/s/{0}.rs\"\nmode = \"test\"\ntopics = {1:?}\n",
+                name, topics
+            ));
+            if let Some(requires) = requires {
+                text.push_str(&format!("requires = {:?}\n", requires));
+            }
+        }
+        toml::from_str(&text).unwrap()
+    }
+
+    fn names(exercises: &[&Exercise]) -> Vec<String> {
+        exercises.iter().map(|e| e.name.clone()).collect()
+    }
+
+    #[test]
+    fn orders_exercises_after_what_they_require() {
+        let list = list(&[
+            ("a", &["x"], Some(&["c"])),
+            ("b", &["y"], Some(&[])),
+            ("c", &["y"], None),
+            ("d", &["x"], Some(&["a", "b"])),
+        ]);
+        assert_eq!(list.check_requirements(), Ok(()));
+        assert_eq!(names(&list.ordered()), ["b", "c", "a", "d"]);
+    }
+
+    #[test]
+    fn paths_include_everything_a_topic_builds_on() {
+        let list = list(&[
+            ("a", &["x"], Some(&[])),
+            ("b", &["y"], Some(&["a"])),
+            ("c", &["z"], Some(&[])),
+            ("d", &["y"], Some(&["b"])),
+        ]);
+        assert_eq!(names(&list.path("y")), ["a", "b", "d"]);
+        assert_eq!(names(&list.path("z")), ["c"]);
+        assert!(list.path("w").is_empty());
+        let topics: Vec<String> = list.topics().into_iter().map(|(t, _)| t).collect();
+        assert_eq!(topics, ["x", "y", "z"]);
+    }
+
+    #[test]
+    fn reports_requirement_cycles() {
+        let itself = list(&[("a", &["x"], Some(&["a"]))]);
+        assert_eq!(
+            itself.check_requirements(),
+            Err("requirements form a cycle: a requires a".to_string())
+        );
+        let around = list(&[
+            ("a", &["x"], Some(&[])),
+            ("b", &["x"], Some(&["d"])),
+            ("c", &["x"], None),
+            ("d", &["x"], Some(&["c"])),
+        ]);
+        assert_eq!(
+            around.check_requirements(),
+            Err("requirements form a cycle: b requires d requires c requires b".to_string())
+        );
+    }
+
+    #[test]
+    fn reports_unknown_requirements() {
+        let list = list(&[("a", &["x"], Some(&["z"]))]);
+        assert_eq!(
+            list.check_requirements(),
+            Err("`a` requires unknown exercise `z`".to_string())
+        );
+    }
+
+    #[test]
+    fn unlocks_exercises_once_their_requirements_pass() {
+        let list = list(&[
+            ("a", &["x"], Some(&[])),
+            ("b", &["x"], Some(&[])),
+            ("c", &["x"], Some(&["a", "b"])),
+        ]);
+        let c = list.find("c").unwrap();
+        let mut progress = Progress::default();
+        assert_eq!(
+            names(&unfinished_requirements(&list, &progress, c)),
+            ["a", "b"]
+        );
+        let record = Record {
+            passed: Some(1),
+            ..Record::default()
+        };
+        progress.exercises.insert("a".to_string(), record);
+        assert_eq!(names(&unfinished_requirements(&list, &progress, c)), ["b"]);
+    }
+}
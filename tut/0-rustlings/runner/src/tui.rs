@@ -0,0 +1,492 @@
+use std::collections::BTreeSet;
+use std::ffi::OsStr;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use ratatui::crossterm::event::{self, Event as Input, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::exercise::{Exercise, ExerciseList, State};
+use crate::package;
+use crate::progress::{self, Progress};
+use crate::project;
+use crate::report::{self, ExerciseReport, Outcome};
+use crate::topics;
+use crate::verify;
+
+const KEYS: &str = " ↑↓ select · enter check · h hint · PgUp/PgDn scroll · q quit ";
+
+/// Something the interface reacts to.
+enum Event {
+    Input(Input),
+    /// A file below `exercises/`, or next to `info.toml`, was saved.
+    Saved(PathBuf),
+    /// The exercise at this index of `info.toml` was checked.
+    Checked(usize, Box<ExerciseReport>),
+}
+
+/// A line of the exercise list: a section heading or an exercise, as its
+/// index in `info.toml`.
+enum Row {
+    Section(String),
+    Exercise(usize),
+}
+
+/// Lists every exercise by section with its status, and shows what the
+/// selected one gets wrong and its hints. Exercises are checked again
+/// whenever one of their files is saved, and saving `info.toml` regenerates
+/// the editor project, as in `watch`.
+pub fn tui(list: &ExerciseList) -> io::Result<()> {
+    let (tx, rx) = channel();
+
+    let (saves, saved) = channel();
+    let mut watcher: RecommendedWatcher =
+        Watcher::new(saves, Duration::from_secs(1)).map_err(io::Error::other)?;
+    watcher
+        .watch(Path::new("exercises"), RecursiveMode::Recursive)
+        .map_err(io::Error::other)?;
+    // Editors often save by renaming over the file, so watch its directory.
+    watcher
+        .watch(Path::new("."), RecursiveMode::NonRecursive)
+        .map_err(io::Error::other)?;
+    let forward = tx.clone();
+    thread::spawn(move || {
+        for event in saved {
+            let path = match event {
+                DebouncedEvent::Create(path)
+                | DebouncedEvent::Write(path)
+                | DebouncedEvent::Chmod(path)
+                | DebouncedEvent::Rename(_, path) => path,
+                _ => continue,
+            };
+            if forward.send(Event::Saved(path)).is_err() {
+                break;
+            }
+        }
+    });
+    let forward = tx.clone();
+    thread::spawn(move || {
+        while let Ok(input) = event::read() {
+            if forward.send(Event::Input(input)).is_err() {
+                break;
+            }
+        }
+    });
+
+    // Check exercises on as many threads as `verify` would, however many
+    // are saved or asked for at once.
+    let (jobs, queue) = channel::<(usize, Exercise)>();
+    let queue = Arc::new(Mutex::new(queue));
+    for _ in 0..verify::Options::default().jobs {
+        let queue = Arc::clone(&queue);
+        let tx = tx.clone();
+        thread::spawn(move || loop {
+            let job = queue.lock().unwrap().recv();
+            let (i, exercise) = match job {
+                Ok(job) => job,
+                Err(_) => break,
+            };
+            let report = report::evaluate(&exercise, None);
+            if tx.send(Event::Checked(i, Box::new(report))).is_err() {
+                break;
+            }
+        });
+    }
+
+    let mut app = App::new(list, jobs);
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal, rx);
+    ratatui::restore();
+    result
+}
+
+struct App<'a> {
+    list: &'a ExerciseList,
+    rows: Vec<Row>,
+    state: ListState,
+    progress: Progress,
+    /// The latest report of each exercise checked since the interface opened.
+    reports: Vec<Option<ExerciseReport>>,
+    /// Exercises being checked, and those saved again while being checked.
+    checking: BTreeSet<usize>,
+    stale: BTreeSet<usize>,
+    /// How many hints of the selected exercise are shown.
+    hints: usize,
+    scroll: u16,
+    /// Why the editor project could not be regenerated, shown in the footer
+    /// until it can.
+    notice: Option<String>,
+    jobs: Sender<(usize, Exercise)>,
+}
+
+impl<'a> App<'a> {
+    fn new(list: &'a ExerciseList, jobs: Sender<(usize, Exercise)>) -> App<'a> {
+        let mut rows = vec![];
+        for (section, exercises) in list.sections() {
+            rows.push(Row::Section(section));
+            for exercise in exercises {
+                let index = list
+                    .exercises
+                    .iter()
+                    .position(|e| e.name == exercise.name)
+                    .unwrap();
+                rows.push(Row::Exercise(index));
+            }
+        }
+        let mut app = App {
+            list,
+            rows,
+            state: ListState::default(),
+            progress: Progress::load().unwrap_or_default(),
+            reports: list.exercises.iter().map(|_| None).collect(),
+            checking: BTreeSet::new(),
+            stale: BTreeSet::new(),
+            hints: 0,
+            scroll: 0,
+            notice: None,
+            jobs,
+        };
+        // Start at the next exercise `status` suggests.
+        let next = list
+            .ordered()
+            .into_iter()
+            .find(|e| !app.passed(&e.name) && app.unlocked(e))
+            .or_else(|| list.exercises.first())
+            .map(|e| e.name.clone());
+        let row = app.rows.iter().position(|row| match row {
+            Row::Exercise(i) => Some(&list.exercises[*i].name) == next.as_ref(),
+            Row::Section(_) => false,
+        });
+        app.state.select(row);
+        if let Some(i) = app.selected() {
+            app.check(i);
+        }
+        app
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal, rx: Receiver<Event>) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            match rx.recv() {
+                Ok(Event::Input(Input::Key(key))) if key.kind == KeyEventKind::Press => {
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                        KeyCode::Up | KeyCode::Char('k') => self.step(-1),
+                        KeyCode::Down | KeyCode::Char('j') => self.step(1),
+                        KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                        KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+                        KeyCode::Enter | KeyCode::Char('r') => {
+                            if let Some(i) = self.selected() {
+                                self.check(i);
+                            }
+                        }
+                        KeyCode::Char('h') => self.reveal_hint(),
+                        _ => {}
+                    }
+                }
+                Ok(Event::Input(_)) => {}
+                Ok(Event::Saved(path)) => self.saved(&path),
+                Ok(Event::Checked(i, report)) => self.checked(i, *report),
+                Err(_) => return Ok(()),
+            }
+        }
+    }
+
+    /// Moves the selection by `delta` exercises, skipping section headings.
+    fn step(&mut self, delta: isize) {
+        let mut row = self.state.selected().unwrap_or(0) as isize;
+        loop {
+            row += delta;
+            if row < 0 || row as usize >= self.rows.len() {
+                return;
+            }
+            if let Row::Exercise(_) = self.rows[row as usize] {
+                break;
+            }
+        }
+        self.state.select(Some(row as usize));
+        self.hints = 0;
+        self.scroll = 0;
+    }
+
+    fn selected(&self) -> Option<usize> {
+        match self.rows.get(self.state.selected()?)? {
+            Row::Exercise(i) => Some(*i),
+            Row::Section(_) => None,
+        }
+    }
+
+    /// Queues the exercise at index `i` to be checked the way `verify`
+    /// checks it, or to be checked once more after the check already under
+    /// way, so that no exercise is checked twice at once.
+    fn check(&mut self, i: usize) {
+        if !self.checking.insert(i) {
+            self.stale.insert(i);
+            return;
+        }
+        let _ = self.jobs.send((i, self.list.exercises[i].clone()));
+    }
+
+    fn checked(&mut self, i: usize, report: ExerciseReport) {
+        self.checking.remove(&i);
+        progress::record_attempt(&report.name, report.outcome == Outcome::Passed);
+        self.progress = Progress::load().unwrap_or_default();
+        self.reports[i] = Some(report);
+        if self.stale.remove(&i) {
+            self.check(i);
+        }
+    }
+
+    /// Checks the exercise a saved file belongs to, and selects it.
+    fn saved(&mut self, path: &Path) {
+        if path.file_name() == Some(OsStr::new(crate::INFO_TOML)) {
+            self.notice = project::regenerate(Path::new(crate::INFO_TOML))
+                .err()
+                .map(|e| format!(" Could not regenerate {}: {} ", project::PROJECT_FILE, e));
+            return;
+        }
+        let source = path.extension() == Some(OsStr::new("rs"))
+            || path.file_name() == Some(OsStr::new(package::MANIFEST));
+        let path = match path.canonicalize() {
+            Ok(path) if source => path,
+            _ => return,
+        };
+        // Files of crate exercises are somewhere below the exercise path.
+        let edited = self
+            .list
+            .exercises
+            .iter()
+            .position(|e| path.ancestors().any(|dir| dir.ends_with(&e.path)));
+        if let Some(i) = edited {
+            if self.selected() != Some(i) {
+                let row = self
+                    .rows
+                    .iter()
+                    .position(|row| matches!(row, Row::Exercise(j) if *j == i));
+                self.state.select(row);
+                self.hints = 0;
+                self.scroll = 0;
+            }
+            self.check(i);
+        }
+    }
+
+    /// Shows the next hint of the selected exercise, like `rustlings hint`.
+    fn reveal_hint(&mut self) {
+        let exercise = match self.selected() {
+            Some(i) => &self.list.exercises[i],
+            None => return,
+        };
+        let count = exercise.hints().len();
+        if count == 0 || self.hints == count {
+            self.hints = count.max(1);
+            return;
+        }
+        let revealed = self.progress.get(&exercise.name).map_or(0, |r| r.hints) as usize;
+        self.hints = if self.hints == 0 {
+            revealed.min(count - 1) + 1
+        } else {
+            self.hints + 1
+        };
+        progress::record_hint(&exercise.name);
+        self.progress = Progress::load().unwrap_or_default();
+    }
+
+    fn passed(&self, name: &str) -> bool {
+        self.progress.get(name).and_then(|r| r.passed).is_some()
+    }
+
+    fn unlocked(&self, exercise: &Exercise) -> bool {
+        topics::unfinished_requirements(self.list, &self.progress, exercise).is_empty()
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Length(32), Constraint::Min(0)]).areas(main);
+
+        let items: Vec<ListItem> = self.rows.iter().map(|row| self.item(row)).collect();
+        let done = self
+            .list
+            .exercises
+            .iter()
+            .filter(|e| self.passed(&e.name))
+            .count();
+        let list = List::new(items)
+            .block(Block::bordered().title(format!(
+                " Exercises {}/{} ",
+                done,
+                self.list.exercises.len()
+            )))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, left, &mut self.state);
+
+        let hints = self.hint_lines();
+        let [diagnostics, hint_area] = if hints.is_empty() {
+            [right, Default::default()]
+        } else {
+            let height = (hints.len() as u16 + 2).min(right.height / 2);
+            Layout::vertical([Constraint::Min(0), Constraint::Length(height)]).areas(right)
+        };
+        let title = match self.selected() {
+            Some(i) => format!(" {} ", self.list.exercises[i]),
+            None => String::new(),
+        };
+        let pane = Paragraph::new(self.diagnostics())
+            .block(Block::bordered().title(title))
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(pane, diagnostics);
+        if !hints.is_empty() {
+            let pane = Paragraph::new(hints)
+                .block(Block::bordered().title(" Hints "))
+                .wrap(Wrap { trim: false });
+            frame.render_widget(pane, hint_area);
+        }
+        let footer_line = match &self.notice {
+            Some(notice) => Paragraph::new(notice.as_str()).style(Style::default().fg(Color::Red)),
+            None => Paragraph::new(KEYS).style(Style::default().add_modifier(Modifier::DIM)),
+        };
+        frame.render_widget(footer_line, footer);
+    }
+
+    fn item(&self, row: &Row) -> ListItem<'static> {
+        let i = match row {
+            Row::Section(section) => {
+                return ListItem::new(Line::styled(
+                    section.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ))
+            }
+            Row::Exercise(i) => *i,
+        };
+        let exercise = &self.list.exercises[i];
+        let outcome = self.reports[i].as_ref().map(|r| r.outcome);
+        let (mark, color) = if self.checking.contains(&i) {
+            ("…", Color::Cyan)
+        } else if outcome == Some(Outcome::Passed)
+            || (outcome.is_none() && self.passed(&exercise.name))
+        {
+            ("✓", Color::Green)
+        } else if outcome == Some(Outcome::Pending) {
+            ("●", Color::Yellow)
+        } else if outcome.is_some() {
+            ("✗", Color::Red)
+        } else if !self.unlocked(exercise) {
+            ("-", Color::DarkGray)
+        } else {
+            ("·", Color::Reset)
+        };
+        ListItem::new(Line::from(vec![
+            Span::styled(format!("  {} ", mark), Style::default().fg(color)),
+            Span::raw(exercise.name.clone()),
+        ]))
+    }
+
+    /// What the selected exercise gets wrong, or why it is locked.
+    fn diagnostics(&self) -> Vec<Line<'static>> {
+        let i = match self.selected() {
+            Some(i) => i,
+            None => return vec![],
+        };
+        let exercise = &self.list.exercises[i];
+        let mut lines = vec![];
+        let unfinished = topics::unfinished_requirements(self.list, &self.progress, exercise);
+        if !unfinished.is_empty() {
+            let names: Vec<&str> = unfinished.iter().map(|e| e.name.as_str()).collect();
+            lines.push(Line::styled(
+                format!("Builds on {}, which is not done yet.", names.join(", ")),
+                Style::default().fg(Color::Yellow),
+            ));
+            lines.push(Line::raw(""));
+        }
+        if self.checking.contains(&i) {
+            lines.push(Line::raw(format!("Checking {}...", exercise)));
+            return lines;
+        }
+        let report = match &self.reports[i] {
+            Some(report) => report,
+            None => {
+                lines.push(Line::raw("Press enter to check this exercise."));
+                return lines;
+            }
+        };
+        match report.outcome {
+            Outcome::Passed => lines.push(Line::styled(
+                format!("{} passed!", exercise),
+                Style::default().fg(Color::Green),
+            )),
+            Outcome::Pending => {
+                lines.push(Line::styled(
+                    "Everything passes! Remove the `I AM NOT DONE` comment to move on:",
+                    Style::default().fg(Color::Green),
+                ));
+                lines.push(Line::raw(""));
+                if let Ok(State::Pending(_, context)) = exercise.state() {
+                    for line in context {
+                        let text = format!("{:>3} |  {}", line.number, line.line);
+                        lines.push(if line.important {
+                            Line::styled(text, Style::default().add_modifier(Modifier::BOLD))
+                        } else {
+                            Line::raw(text)
+                        });
+                    }
+                }
+            }
+            outcome => {
+                lines.push(Line::styled(
+                    format!("{} {}.", exercise, outcome.describe()),
+                    Style::default().fg(Color::Red),
+                ));
+                for detail in report::details(report) {
+                    lines.push(Line::raw(""));
+                    lines.extend(detail.lines().map(|line| Line::raw(line.to_string())));
+                }
+            }
+        }
+        lines
+    }
+
+    fn hint_lines(&self) -> Vec<Line<'static>> {
+        let exercise = match self.selected() {
+            Some(i) if self.hints > 0 => &self.list.exercises[i],
+            _ => return vec![],
+        };
+        let hints = exercise.hints();
+        if hints.is_empty() {
+            return vec![Line::raw(format!(
+                "There are no hints for {}.",
+                exercise.name
+            ))];
+        }
+        let mut lines = vec![];
+        for (level, hint) in hints.iter().enumerate().take(self.hints) {
+            let style = if level + 1 == self.hints {
+                Style::default()
+            } else {
+                Style::default().add_modifier(Modifier::DIM)
+            };
+            if hints.len() > 1 {
+                lines.push(Line::styled(
+                    format!("Hint {} of {}:", level + 1, hints.len()),
+                    style.add_modifier(Modifier::BOLD),
+                ));
+            }
+            lines.extend(
+                hint.lines()
+                    .map(|line| Line::styled(line.to_string(), style)),
+            );
+        }
+        lines
+    }
+}
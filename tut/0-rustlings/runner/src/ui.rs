@@ -0,0 +1,23 @@
+macro_rules! warn {
+    ($fmt:literal, $($arg:tt)*) => {{
+        use console::{style, Emoji};
+        let msg = format!($fmt, $($arg)*);
+        println!("{} {}", style(Emoji("⚠️ ", "!")).red(), style(msg).red());
+    }};
+}
+
+macro_rules! success {
+    ($fmt:literal, $($arg:tt)*) => {{
+        use console::{style, Emoji};
+        let msg = format!($fmt, $($arg)*);
+        println!("{} {}", style(Emoji("✅", "✓")).green(), style(msg).green());
+    }};
+}
+
+macro_rules! progress {
+    ($fmt:literal, $($arg:tt)*) => {{
+        use console::style;
+        let msg = format!($fmt, $($arg)*);
+        println!("{}", style(msg).dim());
+    }};
+}
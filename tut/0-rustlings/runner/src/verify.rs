@@ -0,0 +1,254 @@
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use console::style;
+
+use crate::cache::{self, Cache};
+use crate::exercise::{Exercise, ExerciseList, Mode, State};
+use crate::progress::{self, Progress};
+use crate::report::{self, ExerciseReport, Outcome};
+
+/// How `verify` goes about checking exercises.
+pub struct Options {
+    /// How many exercises are checked at once.
+    pub jobs: usize,
+    /// Whether exercises unchanged since they last passed are skipped.
+    pub cache: bool,
+    /// The exercise to show in detail if it does not pass, rather than the
+    /// first one, such as the one just saved. It is checked even if locked.
+    pub focus: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            jobs: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            cache: true,
+            focus: None,
+        }
+    }
+}
+
+/// Checks every unlocked exercise that is not done: one that fails, edits a
+/// protected region, is over its diff budget, or is still marked
+/// `I AM NOT DONE`. An exercise is unlocked once everything it requires has
+/// passed, in this run or before. Returns the unlocked exercises that are not
+/// done, in order.
+///
+/// Exercises that are unchanged since they last passed are skipped. The rest
+/// are checked up to `options.jobs` at a time without printing anything, in
+/// rounds as passing exercises unlock more. Each that does not pass gets a
+/// line; the focus, or else the first one, is then checked again the same way,
+/// printing why.
+pub fn verify<'a>(
+    list: &ExerciseList,
+    exercises: &[&'a Exercise],
+    options: &Options,
+) -> Result<(), Vec<&'a Exercise>> {
+    let mut cache = if options.cache {
+        Cache::load()
+    } else {
+        Cache::default()
+    };
+    let recorded = Progress::load().unwrap_or_default();
+    let toolchain = cache::toolchain();
+    let mut done: HashSet<String> = HashSet::new();
+    let mut stale = vec![];
+    for exercise in exercises {
+        let fingerprint = cache::fingerprint(exercise, &toolchain);
+        match &fingerprint {
+            Some(f) if cache.passed(&exercise.name, f) => {
+                done.insert(exercise.name.clone());
+            }
+            _ => stale.push((*exercise, fingerprint)),
+        }
+    }
+    if !done.is_empty() {
+        progress!(
+            "Skipped {} exercises unchanged since they passed.",
+            done.len()
+        );
+    }
+
+    let mut failed = vec![];
+    loop {
+        let unlocked = |exercise: &Exercise| {
+            options.focus.as_ref() == Some(&exercise.name)
+                || list.requirements(exercise).iter().all(|r| {
+                    done.contains(&r.name)
+                        || recorded.get(&r.name).is_some_and(|r| r.passed.is_some())
+                })
+        };
+        let (ready, locked): (Vec<_>, Vec<_>) = stale.into_iter().partition(|(e, _)| unlocked(e));
+        stale = locked;
+        if ready.is_empty() {
+            break;
+        }
+        let reports = evaluate(
+            &ready.iter().map(|(e, _)| *e).collect::<Vec<_>>(),
+            options.jobs,
+            None,
+        );
+        for ((exercise, fingerprint), report) in ready.into_iter().zip(reports) {
+            if report.outcome == Outcome::Passed {
+                success!("Successfully verified {}!", exercise);
+                progress::record_attempt(&exercise.name, true);
+                done.insert(exercise.name.clone());
+                if let Some(fingerprint) = fingerprint {
+                    cache.insert(&exercise.name, fingerprint);
+                }
+            } else {
+                failed.push((exercise, report));
+            }
+        }
+    }
+    if options.cache {
+        if let Err(e) = cache.save() {
+            warn!("Could not update {}: {}", cache::CACHE_FILE, e);
+        }
+    }
+    if failed.is_empty() {
+        return Ok(());
+    }
+
+    let shown = failed
+        .iter()
+        .position(|(e, _)| options.focus.as_ref() == Some(&e.name))
+        .unwrap_or(0);
+    for (exercise, report) in failed
+        .iter()
+        .filter(|(e, _)| e.name != failed[shown].0.name)
+    {
+        warn!("{} {}.", exercise, report.outcome.describe());
+    }
+    let locked = stale.len();
+    if failed.len() > 1 || locked > 0 {
+        let (noun, verb, pronoun) = if failed.len() == 1 {
+            ("exercise", "is", "it")
+        } else {
+            ("exercises", "are", "they")
+        };
+        progress!(
+            "{} unlocked {} {} not done yet; {} more unlock once {} {}.",
+            failed.len(),
+            noun,
+            verb,
+            locked,
+            pronoun,
+            verb
+        );
+    }
+    println!();
+
+    // Record an attempt only for the exercise shown, which is the one being
+    // worked on.
+    let exercise = failed[shown].0;
+    let passed = check(exercise).outcome == Outcome::Passed;
+    progress::record_attempt(&exercise.name, passed);
+    let pending: Vec<&Exercise> = failed
+        .iter()
+        .map(|(e, _)| *e)
+        .filter(|e| !(passed && e.name == exercise.name))
+        .collect();
+    if pending.is_empty() {
+        Ok(())
+    } else {
+        Err(pending)
+    }
+}
+
+/// Checks `exercises` on up to `jobs` threads without printing anything,
+/// returning their reports in order. Hidden tests are only run for grading,
+/// from `hidden_dir`.
+pub fn evaluate(
+    exercises: &[&Exercise],
+    jobs: usize,
+    hidden_dir: Option<&Path>,
+) -> Vec<ExerciseReport> {
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new((0..exercises.len()).map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, exercises.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                if i >= exercises.len() {
+                    break;
+                }
+                let report = report::evaluate(exercises[i], hidden_dir);
+                reports.lock().unwrap()[i] = Some(report);
+            });
+        }
+    });
+    reports
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|report| report.expect("every exercise was checked"))
+        .collect()
+}
+
+/// Checks a single exercise, printing what it gets wrong.
+fn check(exercise: &Exercise) -> ExerciseReport {
+    match exercise.mode {
+        Mode::Compile | Mode::Clippy | Mode::Xbuild => progress!("Compiling {}...", exercise),
+        Mode::Test => progress!("Testing {}...", exercise),
+        Mode::Run | Mode::Qemu => progress!("Running {}...", exercise),
+    }
+    let report = report::evaluate_verbosely(exercise);
+    if report.outcome == Outcome::Pending {
+        prompt_for_completion(exercise);
+    }
+    report
+}
+
+/// Prints the surroundings of the `I AM NOT DONE` marker of an exercise
+/// that otherwise passes.
+fn prompt_for_completion(exercise: &Exercise) {
+    let (file, context) = match exercise.state() {
+        Ok(State::Done) => return,
+        Ok(State::Pending(file, context)) => (file, context),
+        Err(e) => {
+            warn!("Could not read {}: {}", exercise, e);
+            return;
+        }
+    };
+
+    let message = match exercise.mode {
+        Mode::Compile => "The code is compiling!",
+        Mode::Test => "The code is compiling, and the tests pass!",
+        Mode::Run => "The code is compiling, and the output is correct!",
+        Mode::Clippy => "The code is compiling, and clippy is happy!",
+        Mode::Xbuild => "The code is compiling for bare metal!",
+        Mode::Qemu => "The code boots, and the serial output is correct!",
+    };
+    println!();
+    println!("🎉 🎉  {} 🎉 🎉", message);
+    println!();
+    println!("You can keep working on this exercise,");
+    if exercise.is_crate() {
+        println!(
+            "or jump into the next one by removing the {} comment in {}:",
+            style("`I AM NOT DONE`").bold(),
+            file.display()
+        );
+    } else {
+        println!(
+            "or jump into the next one by removing the {} comment:",
+            style("`I AM NOT DONE`").bold()
+        );
+    }
+    println!();
+    for line in context {
+        let formatted = format!("{:>3} |  {}", line.number, line.line);
+        if line.important {
+            println!("{}", style(formatted).bold());
+        } else {
+            println!("{}", formatted);
+        }
+    }
+    println!();
+}
@@ -0,0 +1,63 @@
+use std::ffi::OsStr;
+use std::path::Path;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::exercise::{Exercise, ExerciseList};
+use crate::package;
+use crate::project;
+use crate::verify::{verify, Options};
+
+/// Verifies `exercises`, then verifies them again whenever an exercise file is
+/// saved, showing the saved exercise in detail if it is not done. Saving
+/// `info.toml` regenerates the editor project.
+pub fn watch(list: &ExerciseList, exercises: &[&Exercise]) -> notify::Result<()> {
+    let (tx, rx) = channel();
+    let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(1))?;
+    watcher.watch(Path::new("exercises"), RecursiveMode::Recursive)?;
+    // Editors often save by renaming over the file, so watch its directory.
+    watcher.watch(Path::new("."), RecursiveMode::NonRecursive)?;
+
+    verify_all(list, exercises, None);
+    loop {
+        match rx.recv() {
+            Ok(DebouncedEvent::Create(path))
+            | Ok(DebouncedEvent::Write(path))
+            | Ok(DebouncedEvent::Chmod(path))
+            | Ok(DebouncedEvent::Rename(_, path)) => {
+                if path.file_name() == Some(OsStr::new(crate::INFO_TOML)) {
+                    if let Err(e) = project::regenerate(Path::new(crate::INFO_TOML)) {
+                        warn!("Could not regenerate {}: {}", project::PROJECT_FILE, e);
+                    }
+                    continue;
+                }
+                let source = path.extension() == Some(OsStr::new("rs"))
+                    || path.file_name() == Some(OsStr::new(package::MANIFEST));
+                if !source || !path.exists() {
+                    continue;
+                }
+                // Files of crate exercises are somewhere below the exercise path.
+                let path = path.canonicalize()?;
+                let edited = exercises
+                    .iter()
+                    .find(|e| path.ancestors().any(|dir| dir.ends_with(&e.path)));
+                println!("----------**********----------\n");
+                verify_all(list, exercises, edited.map(|e| e.name.clone()));
+            }
+            Ok(_) => {}
+            Err(e) => return Err(notify::Error::Generic(e.to_string())),
+        }
+    }
+}
+
+fn verify_all(list: &ExerciseList, exercises: &[&Exercise], focus: Option<String>) {
+    let options = Options {
+        focus,
+        ..Options::default()
+    };
+    if verify(list, exercises, &options).is_ok() {
+        println!("🎉 All exercises completed! 🎉");
+    }
+}
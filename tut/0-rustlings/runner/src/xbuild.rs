@@ -0,0 +1,105 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::exercise::{CompiledExercise, Exercise, ExerciseOutput};
+
+/// The toolchain pinned by `bin/setup.sh`.
+pub const TOOLCHAIN: &str = "nightly-2019-07-01";
+/// The target of the lab kernels.
+pub const DEFAULT_TARGET: &str = "aarch64-unknown-none";
+
+const NO_STD_REGEX: &str = r"(?m)^\s*#!\[no_std\]";
+
+/// Cross-compiles an `xbuild` exercise as a `no_std` library.
+///
+/// The exercise file becomes the root of a scratch crate, which is built
+/// with `cargo xbuild` on the pinned toolchain. `std` does not exist for the
+/// target, so any use of it fails to compile.
+pub fn build_lib(exercise: &Exercise) -> Result<CompiledExercise, ExerciseOutput> {
+    let source = fs::read_to_string(&exercise.path)?;
+    if !regex::Regex::new(NO_STD_REGEX).unwrap().is_match(&source) {
+        return Err(ExerciseOutput {
+            stdout: String::new(),
+            stderr: format!(
+                "{} must start with `#![no_std]`: there is no `std` on {}.",
+                exercise,
+                target(exercise)
+            ),
+            status: None,
+            violation: None,
+        });
+    }
+
+    let scratch = exercise.temp_binary();
+    let root = fs::canonicalize(&exercise.path)?;
+    let lib = format!("[lib]\npath = \"{}\"", root.display());
+    let manifest = write_manifest(exercise, &scratch, &lib)?;
+    let compiled = CompiledExercise::in_scratch(scratch.join("lib.rlib"), scratch);
+
+    let output = cargo("xbuild", exercise, &manifest).output()?;
+    if output.status.success() {
+        Ok(compiled)
+    } else {
+        Err(output.into())
+    }
+}
+
+/// Returns the target `exercise` is cross-compiled for.
+pub fn target(exercise: &Exercise) -> &str {
+    exercise.target.as_deref().unwrap_or(DEFAULT_TARGET)
+}
+
+/// Returns a `cargo <subcommand>` invocation on the pinned toolchain that
+/// cross-compiles the scratch crate at `manifest` for `exercise`'s target.
+pub fn cargo(subcommand: &str, exercise: &Exercise, manifest: &Path) -> Command {
+    let mut cmd = Command::new("cargo");
+    cmd.arg(format!("+{}", TOOLCHAIN))
+        .arg(subcommand)
+        .arg("--manifest-path")
+        .arg(manifest)
+        .arg("--target")
+        .arg(target(exercise))
+        .args(["--color", "always"])
+        .env("CARGO_TARGET_DIR", shared_target_dir());
+    cmd
+}
+
+/// Returns the directory `cargo` puts `exercise`'s cross-compiled binaries in.
+pub fn artifact_dir(exercise: &Exercise) -> PathBuf {
+    shared_target_dir().join(target(exercise)).join("debug")
+}
+
+/// Creates `scratch` and writes a manifest into it for a scratch crate with
+/// the given `[lib]` or `[[bin]]` section.
+pub fn write_manifest(exercise: &Exercise, scratch: &Path, section: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(scratch)?;
+    let manifest = scratch.join("Cargo.toml");
+    let contents = format!(
+        r#"[package]
+name = "{name}"
+version = "0.0.0"
+edition = "2018"
+
+{section}
+
+[profile.dev]
+panic = "abort"
+
+[profile.release]
+panic = "abort"
+"#,
+        name = exercise.name,
+        section = section
+    );
+    fs::write(&manifest, contents)?;
+    Ok(manifest)
+}
+
+/// A target directory shared by every build so that `cargo xbuild` only
+/// builds the `core` and `alloc` sysroot once.
+fn shared_target_dir() -> PathBuf {
+    env::temp_dir().join("rustlings-xbuild")
+}
@@ -0,0 +1,107 @@
+// Boot stub for `qemu` exercises.
+//
+// The runner builds this file as the root of a scratch crate with the
+// exercise `include!`d below, and boots it on QEMU's raspi3 machine. Core 0
+// zeroes `.bss`, calls the exercise's `main`, and exits QEMU through
+// semihosting. `print!` and `println!` write to the PL011 UART, which QEMU
+// connects to stdout.
+
+#![no_std]
+#![no_main]
+#![feature(global_asm)]
+
+use core::fmt::{self, Write};
+use core::panic::PanicInfo;
+use core::ptr::{read_volatile, write_volatile};
+
+global_asm!(
+    r#"
+.section .text.boot
+.global _start
+_start:
+    mrs     x1, mpidr_el1
+    and     x1, x1, #3
+    cbz     x1, 2f
+1:  wfe
+    b       1b
+2:  ldr     x1, =_start
+    mov     sp, x1
+    bl      rustlings_boot
+    b       1b
+
+.section .text
+.global rustlings_exit
+rustlings_exit:
+    ldr     x1, =0x20026
+    stp     x1, x0, [sp, #-16]!
+    mov     x1, sp
+    mov     w0, #0x18
+    hlt     #0xf000
+3:  b       3b
+"#
+);
+
+extern "C" {
+    static mut __bss_start: u64;
+    static mut __bss_end: u64;
+
+    /// Exits QEMU with `status` (semihosting `SYS_EXIT`).
+    fn rustlings_exit(status: u64) -> !;
+}
+
+const UART_DR: *mut u32 = 0x3F20_1000 as *mut u32;
+const UART_FR: *const u32 = 0x3F20_1018 as *const u32;
+const UART_FR_TXFF: u32 = 1 << 5;
+
+struct Uart;
+
+impl Write for Uart {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            unsafe {
+                while read_volatile(UART_FR) & UART_FR_TXFF != 0 {}
+                write_volatile(UART_DR, u32::from(byte));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    let _ = Uart.write_fmt(args);
+}
+
+#[allow(unused_macros)]
+macro_rules! print {
+    ($($arg:tt)*) => ($crate::_print(format_args!($($arg)*)));
+}
+
+#[allow(unused_macros)]
+macro_rules! println {
+    () => (print!("\n"));
+    ($($arg:tt)*) => ({
+        $crate::_print(format_args!($($arg)*));
+        print!("\n");
+    });
+}
+
+include!("{exercise}");
+
+#[no_mangle]
+pub unsafe extern "C" fn rustlings_boot() -> ! {
+    let mut word = &mut __bss_start as *mut u64;
+    while word < &mut __bss_end as *mut u64 {
+        write_volatile(word, 0);
+        word = word.add(1);
+    }
+
+    main();
+    rustlings_exit(0)
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    println!("{}", info);
+    unsafe { rustlings_exit(101) }
+}
@@ -0,0 +1,17 @@
+// FIXME: Make me compile! Diff budget: 1 line.
+
+// What traits does this struct need to derive?
+#[derive(Debug, Clone, Copy)]
+enum Duration {
+    MilliSeconds(u64),
+    Seconds(u32),
+    Minutes(u16),
+}
+
+pub fn main() {
+    println!("Duration: {:?}", Duration::MilliSeconds(1200));
+
+    let x = Duration::Minutes(10);
+    let y = x;
+    let z = x;
+}
@@ -0,0 +1,10 @@
+// functions1.rs
+// Make me compile! Execute `rustlings hint functions1` for hints :)
+
+fn main() {
+    call_me();
+}
+
+fn call_me() {
+    println!("Hello from call_me!");
+}
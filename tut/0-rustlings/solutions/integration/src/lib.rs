@@ -0,0 +1,5 @@
+// integration: a library tested from the outside, by the tests in `tests/`.
+// Make the tests pass! Execute `rustlings hint integration` for hints :)
+// Do not change the tests.
+
+pub mod point;
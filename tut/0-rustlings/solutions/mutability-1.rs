@@ -0,0 +1,7 @@
+// FIXME: Make me compile! Diff budget: 1 line.
+
+fn make_1(v: &mut u32) {
+    *v = 1;
+}
+
+pub fn main() {}
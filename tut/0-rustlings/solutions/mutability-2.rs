@@ -0,0 +1,6 @@
+// FIXME: Make me compile! Diff budget: 1 line.
+
+pub fn main() {
+    let x = &mut 10;
+    *x = 20;
+}
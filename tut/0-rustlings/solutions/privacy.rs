@@ -0,0 +1,10 @@
+// FIXME: Make me compile! Diff budget: 1 line.
+
+mod a {
+    pub fn f() {}
+}
+
+// Do not modify this function.
+fn main() {
+    a::f();
+}
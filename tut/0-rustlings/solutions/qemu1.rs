@@ -0,0 +1,21 @@
+// FIXME: Make me pass! Diff budget: 3 lines.
+
+// qemu1.rs
+// This program boots on a (virtual) Raspberry Pi. There is no operating
+// system and no heap: `println!` writes straight to the Pi's serial port,
+// which the runner compares with the memory map we expect to see.
+// Execute `rustlings hint qemu1` for hints!
+
+fn main() {
+    let mut regions = [
+        ("uart", 0x3F20_1000u64, 0x3F20_1090u64),
+        ("kernel", 0x8_0000, 0x10_0000),
+        ("gpio", 0x3F20_0000, 0x3F20_00B4),
+    ];
+    regions.sort_unstable_by_key(|&(_, start, _)| start);
+
+    println!("Memory map:");
+    for &(name, start, end) in regions.iter() {
+        println!("{:>6}: {:#010x}..{:#010x} ({} bytes)", name, start, end, end - start);
+    }
+}
@@ -0,0 +1,7 @@
+// FIXME: Make me compile! Diff budget: 1 character.
+
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+// tests4.rs
+// `collatz_steps` counts how many steps it takes to get from `n` down to 1,
+// printing each step so that we can see what it is doing. But one of the
+// tests never finishes: the runner stops it once it has printed too much.
+// Fix `collatz_steps`, not the tests!
+// Execute `rustlings hint tests4` for hints :)
+
+pub fn collatz_steps(mut n: u64) -> u32 {
+    let mut steps = 0;
+    while n != 1 {
+        println!("step {}: {}", steps, n);
+        n = if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+        steps += 1;
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_takes_no_steps() {
+        assert_eq!(collatz_steps(1), 0);
+    }
+
+    #[test]
+    fn six_takes_eight_steps() {
+        assert_eq!(collatz_steps(6), 8);
+    }
+}
@@ -0,0 +1,33 @@
+// FIXME: Make me pass! Diff budget: 25 lines.
+
+#[derive(Debug)]
+enum Duration {
+    MilliSeconds(u64),
+    Seconds(u32),
+    Minutes(u16),
+}
+
+// What traits does `Duration` need to implement?
+impl Duration {
+    fn as_millis(&self) -> u64 {
+        match *self {
+            Duration::MilliSeconds(ms) => ms,
+            Duration::Seconds(s) => u64::from(s) * 1000,
+            Duration::Minutes(m) => u64::from(m) * 60 * 1000,
+        }
+    }
+}
+
+impl PartialEq for Duration {
+    fn eq(&self, other: &Duration) -> bool {
+        self.as_millis() == other.as_millis()
+    }
+}
+
+#[test]
+fn traits() {
+    assert_eq!(Duration::Seconds(120), Duration::Minutes(2));
+    assert_eq!(Duration::Seconds(420), Duration::Minutes(7));
+    assert_eq!(Duration::MilliSeconds(420000), Duration::Minutes(7));
+    assert_eq!(Duration::MilliSeconds(43000), Duration::Seconds(43));
+}
@@ -0,0 +1,9 @@
+pub struct Config {
+    pub(crate) port: u16,
+}
+
+impl Config {
+    pub fn new(port: u16) -> Config {
+        Config { port }
+    }
+}
@@ -0,0 +1,11 @@
+// visibility: a crate whose modules live in their own files.
+// Make me compile! Execute `rustlings hint visibility` for hints :)
+// The fix belongs in `src/config.rs`; do not change the other files.
+
+mod config;
+mod server;
+
+fn main() {
+    let config = config::Config::new(8080);
+    server::start(&config);
+}
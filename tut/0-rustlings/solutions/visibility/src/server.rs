@@ -0,0 +1,7 @@
+mod log;
+
+use crate::config::Config;
+
+pub fn start(config: &Config) {
+    log::info(&format!("listening on port {}", config.port));
+}